@@ -42,6 +42,13 @@ pub enum Stroke {
     WordBreak,
     PhraseBreak,
     SentenceBreak,
+    ListBreak,
+    QuestionBreak,
+    ExclamationBreak,
+    OpenQuotation,
+    CloseQuotation,
+    OpenParenthetical,
+    CloseParenthetical,
 }
 
 pub trait ToStroke {
@@ -432,6 +439,18 @@ impl ToRects for Stroke {
             h: printer.page.short_stroke_length(),
         };
 
+        let enclosure_l = Rect {
+            x: word_break_rect.x,
+            y: top_right.1,
+            w: printer.page.stroke_width(),
+            h: printer.page.punctuation_height(),
+        };
+
+        let enclosure_r = Rect {
+            x: sentence_break_r.x,
+            ..enclosure_l
+        };
+
         match self {
             Stroke::Nil => vec![],
             Stroke::P => vec![top_long_stroke_rect, p_wing_l_rect, p_wing_r_rect],
@@ -529,6 +548,23 @@ impl ToRects for Stroke {
             Stroke::WordBreak => vec![word_break_rect],
             Stroke::PhraseBreak => vec![phrase_break_top, phrase_break_bottom],
             Stroke::SentenceBreak => vec![word_break_rect, sentence_break_l, sentence_break_r],
+            Stroke::ListBreak => vec![phrase_break_top, word_break_rect],
+            Stroke::QuestionBreak => vec![
+                phrase_break_top,
+                word_break_rect,
+                sentence_break_l,
+                sentence_break_r,
+            ],
+            Stroke::ExclamationBreak => vec![
+                word_break_rect,
+                sentence_break_l,
+                sentence_break_r,
+                phrase_break_bottom,
+            ],
+            Stroke::OpenQuotation => vec![enclosure_l, phrase_break_top],
+            Stroke::CloseQuotation => vec![enclosure_r, phrase_break_bottom],
+            Stroke::OpenParenthetical => vec![enclosure_l, phrase_break_top, phrase_break_bottom],
+            Stroke::CloseParenthetical => vec![enclosure_r, phrase_break_top, phrase_break_bottom],
         }
     }
 }
@@ -964,6 +1000,13 @@ mod strokes {
                 wa::Punctuation::WordBreak => Stroke::WordBreak,
                 wa::Punctuation::PhraseBreak => Stroke::PhraseBreak,
                 wa::Punctuation::SentenceBreak => Stroke::SentenceBreak,
+                wa::Punctuation::ListBreak => Stroke::ListBreak,
+                wa::Punctuation::QuestionBreak => Stroke::QuestionBreak,
+                wa::Punctuation::ExclamationBreak => Stroke::ExclamationBreak,
+                wa::Punctuation::OpenQuotation => Stroke::OpenQuotation,
+                wa::Punctuation::CloseQuotation => Stroke::CloseQuotation,
+                wa::Punctuation::OpenParenthetical => Stroke::OpenParenthetical,
+                wa::Punctuation::CloseParenthetical => Stroke::CloseParenthetical,
             }
         }
     }
@@ -1005,25 +1048,53 @@ mod strokes {
         }
     }
 
+    // the terminator is left to the paragraph
     impl ToGraphemes for wa::Sentence {
         fn to_graphemes(&self) -> Vec<Grapheme> {
-            self.0
-                .iter()
-                .map(ToGraphemes::to_graphemes)
-                .intersperse_with(|| vec![Punctuation::PhraseBreak.to_grapheme()])
-                .flatten()
-                .collect()
+            let mut graphemes = Vec::new();
+            let mut words = 0;
+
+            for (i, phrase) in self.phrases.iter().enumerate() {
+                if i > 0 {
+                    graphemes.push(self.separator(i).punctuation().to_grapheme());
+                }
+
+                for (j, word) in phrase.0.iter().enumerate() {
+                    if j > 0 {
+                        graphemes.push(Punctuation::WordBreak.to_grapheme());
+                    }
+
+                    for span in self.spans.iter().filter(|span| span.start == words) {
+                        graphemes.push(span.enclosure.open().to_grapheme());
+                    }
+
+                    graphemes.extend(word.to_graphemes());
+                    words += 1;
+
+                    for span in self.spans.iter().rev().filter(|span| span.end == words) {
+                        graphemes.push(span.enclosure.close().to_grapheme());
+                    }
+                }
+            }
+
+            graphemes
         }
     }
 
+    // sentences are followed by their terminators, except for a statement ending the paragraph
     impl ToGraphemes for wa::Paragraph {
         fn to_graphemes(&self) -> Vec<Grapheme> {
-            self.0
-                .iter()
-                .map(ToGraphemes::to_graphemes)
-                .intersperse_with(|| vec![Punctuation::SentenceBreak.to_grapheme()])
-                .flatten()
-                .collect()
+            let mut graphemes = Vec::new();
+
+            for (i, sentence) in self.0.iter().enumerate() {
+                graphemes.extend(sentence.to_graphemes());
+
+                if i + 1 < self.0.len() || sentence.terminator != wa::Terminator::Statement {
+                    graphemes.push(sentence.terminator.punctuation().to_grapheme());
+                }
+            }
+
+            graphemes
         }
     }
 
@@ -2,7 +2,11 @@ use std::collections::HashMap;
 
 use derive_more::{Deref, DerefMut};
 use serde_derive::{Deserialize, Serialize};
-use serde_with::serde_as;
+use wa::{
+    collation::{Collate, Order},
+    distance::Weights,
+    sound_change::{Derivation, History},
+};
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Definition {
@@ -11,22 +15,134 @@ pub struct Definition {
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct DictionaryEntry(pub wa::Syllable, pub Vec<Definition>);
+pub struct DictionaryEntry(
+    #[serde(with = "wa::serde::compact")] pub wa::Syllable,
+    pub Vec<Definition>,
+);
 
-#[serde_as]
 #[derive(Deref, DerefMut, Serialize, Deserialize)]
 pub struct Dictionary {
     #[deref]
-    #[serde_as(as = "Vec<(_, _)>")]
+    #[serde(with = "entries")]
     entries: HashMap<wa::Syllable, Vec<Definition>>,
 }
 
+// entries are written as a map from each syllable's notation, in native order so that saves diff
+// well.
+// older dictionaries stored a list of (verbose syllable, definitions) pairs, those still load
+mod entries {
+    use std::{collections::HashMap, fmt};
+
+    use serde::{
+        de::{MapAccess, SeqAccess, Visitor},
+        Deserializer, Serializer,
+    };
+    use wa::{
+        collation::{Collate, Order},
+        serde::Compact,
+    };
+
+    use crate::Definition;
+
+    pub fn serialize<S: Serializer>(
+        entries: &HashMap<wa::Syllable, Vec<Definition>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut entries = entries.iter().collect::<Vec<_>>();
+        entries.sort_by_cached_key(|(syllable, _)| syllable.sort_key(Order::Native));
+
+        serializer.collect_map(
+            entries
+                .into_iter()
+                .map(|(syllable, definitions)| (Compact(*syllable), definitions)),
+        )
+    }
+
+    // either form, an entry that doesn't load fails with its own error
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<wa::Syllable, Vec<Definition>>, D::Error> {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = HashMap<wa::Syllable, Vec<Definition>>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map from syllables to definitions, or a list of pairs of them")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = HashMap::new();
+
+                while let Some((syllable, definitions)) =
+                    map.next_entry::<Compact<wa::Syllable>, Vec<Definition>>()?
+                {
+                    entries.insert(syllable.0, definitions);
+                }
+
+                Ok(entries)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut entries = HashMap::new();
+
+                while let Some((syllable, definitions)) =
+                    seq.next_element::<(Compact<wa::Syllable>, Vec<Definition>)>()?
+                {
+                    entries.insert(syllable.0, definitions);
+                }
+
+                Ok(entries)
+            }
+        }
+
+        deserializer.deserialize_any(EntriesVisitor)
+    }
+}
+
 impl Dictionary {
     pub fn new() -> Self {
         Self {
             entries: HashMap::new(),
         }
     }
+
+    // the dictionary after a history of sound changes, with the derivation of every entry in
+    // native order. entries that merge keep all their definitions, in that order
+    pub fn develop(&self, history: &History) -> (Dictionary, Vec<Derivation>) {
+        let mut syllables = self.entries.keys().collect::<Vec<_>>();
+        syllables.sort_by_cached_key(|syllable| syllable.sort_key(Order::Native));
+
+        let mut developed = Dictionary::new();
+        let mut derivations = Vec::new();
+
+        for syllable in syllables {
+            let derivation = history.derive(&wa::Word(vec![*syllable]));
+
+            developed
+                .entries
+                .entry(derivation.output()[0])
+                .or_default()
+                .extend(self.entries[syllable].iter().cloned());
+
+            derivations.push(derivation);
+        }
+
+        (developed, derivations)
+    }
+
+    // the `k` entries that sound closest to `query`, closest first, with how far each is. entries
+    // equally far apart are in native order
+    pub fn search(&self, query: &wa::Syllable, k: usize) -> Vec<(wa::Syllable, f64)> {
+        let mut syllables = self.entries.keys().collect::<Vec<_>>();
+        syllables.sort_by_cached_key(|syllable| syllable.sort_key(Order::Native));
+
+        Weights::standard()
+            .nearest(query, syllables, k)
+            .into_iter()
+            .map(|(syllable, distance)| (*syllable, distance))
+            .collect()
+    }
 }
 
 impl Default for Dictionary {
@@ -7,7 +7,7 @@ use std::{
 
 use eframe::{egui, App, AppCreator, CreationContext, NativeOptions};
 use kyay::{Definition, Dictionary, DictionaryEntry};
-use wa::Ipa;
+use wa::{collation::Order, Ipa};
 
 enum HistoryItem {
     AddWord(wa::Syllable),
@@ -30,11 +30,16 @@ struct KyayApp {
     history: Vec<HistoryItem>,
 
     add_word_text: String,
+    add_word_error: Option<String>,
     add_tag_text: String,
 
     tag_filter: String,
+    search_text: String,
 }
 
+// how many of the closest entries a search lists
+const SEARCH_RESULTS: usize = 10;
+
 impl KyayApp {
     pub fn save_to_json(&self) {
         let writer = BufWriter::new(
@@ -173,13 +178,21 @@ impl App for KyayApp {
                     .lost_focus()
                     && ctx.input(|input| input.key_pressed(egui::Key::Enter))
                 {
-                    if let Ok(word) = self.add_word_text.parse::<wa::Syllable>() {
-                        self.add_word_text = String::new();
+                    match self.add_word_text.parse::<wa::Syllable>() {
+                        Ok(word) => {
+                            self.add_word_text = String::new();
+                            self.add_word_error = None;
 
-                        self.add_word(word);
+                            self.add_word(word);
+                        }
+                        Err(error) => self.add_word_error = Some(error.to_string()),
                     }
                 }
 
+                if let Some(error) = &self.add_word_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+
                 ui.separator();
 
                 ui.add(
@@ -188,6 +201,12 @@ impl App for KyayApp {
 
                 ui.separator();
 
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.search_text).hint_text("Search sounds..."),
+                );
+
+                ui.separator();
+
                 if ui.button("Save").clicked() {
                     self.save_to_json();
                 }
@@ -205,8 +224,29 @@ impl App for KyayApp {
                 .filter(|s| !s.is_empty()),
         );
 
+        // anything after the syllable is ignored, so a search can be typed a letter at a time
+        let search =
+            wa::parse_with::<wa::Syllable>(self.search_text.trim(), wa::ParseMode::Lenient).ok();
+
         egui::SidePanel::new(egui::panel::Side::Left, "words_panel").show(ctx, |ui| {
             egui::ScrollArea::vertical().show(ui, |ui| {
+                if let Some(query) = &search {
+                    for (word, distance) in self.dictionary.search(query, SEARCH_RESULTS) {
+                        let is_selected = Some(word) == self.selected;
+
+                        ui.with_layout(egui::Layout::top_down_justified(egui::Align::Min), |ui| {
+                            if ui
+                                .selectable_label(is_selected, format!("{word} ({distance:.2})"))
+                                .clicked()
+                            {
+                                self.selected = Some(word);
+                            }
+                        });
+                    }
+
+                    ui.separator();
+                }
+
                 let mut words = self
                     .dictionary
                     .iter()
@@ -226,7 +266,7 @@ impl App for KyayApp {
                     })
                     .map(|(k, _)| k)
                     .collect::<Vec<_>>();
-                words.sort();
+                Order::Native.sort(&mut words);
 
                 for word in words {
                     let word_txt = word.to_string();
@@ -417,8 +457,10 @@ fn main() {
                 history: Vec::new(),
                 selected: None,
                 add_word_text: String::new(),
+                add_word_error: None,
                 add_tag_text: String::new(),
                 tag_filter: String::new(),
+                search_text: String::new(),
             })
         }),
     )
@@ -0,0 +1,40 @@
+use kyay::Dictionary;
+
+#[test]
+fn entries_load_from_a_map() {
+    let json = r#"{"entries": {"ká": [{"tags": [], "definition": "one"}]}}"#;
+    let dictionary: Dictionary = serde_json::from_str(json).unwrap();
+
+    assert_eq!(dictionary[&wa::syllable("ka,")][0].definition, "one");
+}
+
+#[test]
+fn entries_load_from_older_pairs() {
+    let syllable = serde_json::to_string(&wa::syllable("ka,")).unwrap();
+    let json = format!(r#"{{"entries": [[{syllable}, [{{"tags": [], "definition": "one"}}]]]}}"#);
+    let dictionary: Dictionary = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(dictionary[&wa::syllable("ka,")][0].definition, "one");
+}
+
+#[test]
+fn a_bad_syllable_says_what_is_wrong_with_it() {
+    let json = r#"{"entries": {"ká": [], "qá": []}}"#;
+    let error = serde_json::from_str::<Dictionary>(json)
+        .err()
+        .unwrap()
+        .to_string();
+
+    assert!(error.starts_with(r#""qá": expected an onset"#), "{error}");
+}
+
+#[test]
+fn a_bad_definition_says_what_is_wrong_with_it() {
+    let json = r#"{"entries": {"ká": [{"tags": []}]}}"#;
+    let error = serde_json::from_str::<Dictionary>(json)
+        .err()
+        .unwrap()
+        .to_string();
+
+    assert!(error.contains("missing field `definition`"), "{error}");
+}
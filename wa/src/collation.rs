@@ -0,0 +1,169 @@
+use std::cmp::Ordering;
+
+use serde_derive::*;
+
+use crate::{CStem, PureC, Syllable, Word, C, H, T, V};
+
+// syllables are compared letter by letter with the tones ignored, and only when every letter ties
+// are the tones compared, first syllable first. so all the readings of "ka-xu" sort together, no
+// matter their tones
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Order {
+    // the order of the native script, which is the order dux lists its strokes in (`dux::Stroke`):
+    //   onsets  p b py t d ty k g ky s z sy r rw l w y x h
+    //   vowels  a e i o u
+    //   codas   (none) w y x h
+    // a syllable without a coda sorts before the ones with, like a shorter word does
+    #[default]
+    Native,
+    // the alphabetical order of the romanisation, a digraph sorts right after its first letter:
+    //   onsets  b d g h k ky l p py r rw s sy t ty w x y z
+    //   vowels  a e i o u
+    //   codas   (none) h w x y
+    Romanized,
+}
+
+// both orders rank the tones high, low, peaking, nasal
+const TONES: [T; T::COUNT] = [T::High, T::Low, T::Peaking, T::Nasal];
+
+const NATIVE_ONSETS: [C; C::COUNT] = [
+    C::C(PureC::Strong(CStem::P)),
+    C::C(PureC::Blunt(CStem::P)),
+    C::C(PureC::Sharp(CStem::P)),
+    C::C(PureC::Strong(CStem::T)),
+    C::C(PureC::Blunt(CStem::T)),
+    C::C(PureC::Sharp(CStem::T)),
+    C::C(PureC::Strong(CStem::K)),
+    C::C(PureC::Blunt(CStem::K)),
+    C::C(PureC::Sharp(CStem::K)),
+    C::C(PureC::Strong(CStem::S)),
+    C::C(PureC::Blunt(CStem::S)),
+    C::C(PureC::Sharp(CStem::S)),
+    C::C(PureC::Strong(CStem::R)),
+    C::C(PureC::Blunt(CStem::R)),
+    C::C(PureC::Sharp(CStem::R)),
+    C::H(H::W),
+    C::H(H::Y),
+    C::H(H::X),
+    C::H(H::H),
+];
+
+const ROMANIZED_ONSETS: [C; C::COUNT] = [
+    C::C(PureC::Blunt(CStem::P)),
+    C::C(PureC::Blunt(CStem::T)),
+    C::C(PureC::Blunt(CStem::K)),
+    C::H(H::H),
+    C::C(PureC::Strong(CStem::K)),
+    C::C(PureC::Sharp(CStem::K)),
+    C::C(PureC::Sharp(CStem::R)),
+    C::C(PureC::Strong(CStem::P)),
+    C::C(PureC::Sharp(CStem::P)),
+    C::C(PureC::Strong(CStem::R)),
+    C::C(PureC::Blunt(CStem::R)),
+    C::C(PureC::Strong(CStem::S)),
+    C::C(PureC::Sharp(CStem::S)),
+    C::C(PureC::Strong(CStem::T)),
+    C::C(PureC::Sharp(CStem::T)),
+    C::H(H::W),
+    C::H(H::X),
+    C::H(H::Y),
+    C::C(PureC::Blunt(CStem::S)),
+];
+
+const VOWELS: [V; V::COUNT] = [V::A, V::E, V::I, V::O, V::U];
+
+const NATIVE_CODAS: [Option<H>; H::COUNT + 1] =
+    [None, Some(H::W), Some(H::Y), Some(H::X), Some(H::H)];
+
+const ROMANIZED_CODAS: [Option<H>; H::COUNT + 1] =
+    [None, Some(H::H), Some(H::W), Some(H::X), Some(H::Y)];
+
+impl Order {
+    pub fn onsets(&self) -> &'static [C] {
+        match self {
+            Order::Native => &NATIVE_ONSETS,
+            Order::Romanized => &ROMANIZED_ONSETS,
+        }
+    }
+
+    pub fn vowels(&self) -> &'static [V] {
+        &VOWELS
+    }
+
+    pub fn tones(&self) -> &'static [T] {
+        &TONES
+    }
+
+    pub fn codas(&self) -> &'static [Option<H>] {
+        match self {
+            Order::Native => &NATIVE_CODAS,
+            Order::Romanized => &ROMANIZED_CODAS,
+        }
+    }
+
+    pub fn compare<N: Collate + ?Sized>(&self, a: &N, b: &N) -> Ordering {
+        a.sort_key(*self).cmp(&b.sort_key(*self))
+    }
+
+    pub fn sort<N: Collate>(&self, nodes: &mut [N]) {
+        nodes.sort_by_cached_key(|node| node.sort_key(*self))
+    }
+}
+
+// every order lists every value, the collation tests check that
+fn rank<X: PartialEq>(list: &[X], x: &X) -> u8 {
+    list.iter().position(|y| y == x).unwrap() as u8
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortKey {
+    // (onset, vowel, coda) ranks of each syllable
+    letters: Vec<[u8; 3]>,
+    tones: Vec<u8>,
+}
+
+impl SortKey {
+    fn push(&mut self, syllable: &Syllable, order: Order) {
+        self.letters.push([
+            rank(order.onsets(), &syllable.onset),
+            rank(order.vowels(), &syllable.vowel),
+            rank(order.codas(), &syllable.coda),
+        ]);
+        self.tones.push(rank(order.tones(), &syllable.tone));
+    }
+
+    // the tone-insensitive part of the key, two nodes that only differ in tone have the same one
+    pub fn primary(&self) -> &[[u8; 3]] {
+        &self.letters
+    }
+}
+
+pub trait Collate {
+    fn sort_key(&self, order: Order) -> SortKey;
+}
+
+impl Collate for Syllable {
+    fn sort_key(&self, order: Order) -> SortKey {
+        let mut key = SortKey::default();
+        key.push(self, order);
+        key
+    }
+}
+
+impl Collate for Word {
+    fn sort_key(&self, order: Order) -> SortKey {
+        let mut key = SortKey::default();
+
+        for syllable in self.iter() {
+            key.push(syllable, order);
+        }
+
+        key
+    }
+}
+
+impl<N: Collate + ?Sized> Collate for &N {
+    fn sort_key(&self, order: Order) -> SortKey {
+        (**self).sort_key(order)
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    fmt::{self, Display, Write},
+    sync::OnceLock,
+};
+
+use serde_derive::*;
+
+use crate::{
+    features::{Features, Phoneme},
+    Syllable, Word, C, H, T, V,
+};
+
+// what each difference costs. two segments cost the sum of the features they differ in, so t and d
+// (voicing and aspiration) are closer than t and k (place)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Weights {
+    pub place: f64,
+    pub manner: f64,
+    pub voicing: f64,
+    pub aspiration: f64,
+    pub palatalization: f64,
+    pub height: f64,
+    pub backness: f64,
+    pub rounding: f64,
+
+    pub tone: f64,
+    // a coda against none
+    pub coda: f64,
+    // a syllable one word has and the other doesn't
+    pub syllable: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            place: 1.,
+            manner: 1.,
+            voicing: 0.25,
+            aspiration: 0.25,
+            palatalization: 0.25,
+            height: 0.5,
+            backness: 0.5,
+            rounding: 0.25,
+
+            tone: 0.5,
+            coda: 0.5,
+            syllable: 3.,
+        }
+    }
+}
+
+impl Weights {
+    // the weights behind `Distance::distance`
+    pub fn standard() -> &'static Weights {
+        static STANDARD: OnceLock<Weights> = OnceLock::new();
+
+        STANDARD.get_or_init(Weights::default)
+    }
+
+    pub fn features(&self, a: &Features, b: &Features) -> f64 {
+        let mut cost = 0.;
+
+        let mut add = |differs: bool, weight: f64| {
+            if differs {
+                cost += weight;
+            }
+        };
+
+        add(a.place != b.place, self.place);
+        add(a.manner != b.manner, self.manner);
+        add(a.voiced != b.voiced, self.voicing);
+        add(a.aspirated != b.aspirated, self.aspiration);
+        add(a.palatalized != b.palatalized, self.palatalization);
+        add(a.height != b.height, self.height);
+        add(a.backness != b.backness, self.backness);
+        add(a.rounded != b.rounded, self.rounding);
+
+        cost
+    }
+
+    pub fn segment(&self, a: &impl Phoneme, b: &impl Phoneme) -> f64 {
+        self.features(&a.features(), &b.features())
+    }
+
+    pub fn tones(&self, a: T, b: T) -> f64 {
+        if a == b {
+            0.
+        } else {
+            self.tone
+        }
+    }
+
+    pub fn codas(&self, a: Option<H>, b: Option<H>) -> f64 {
+        match (a, b) {
+            (None, None) => 0.,
+            (Some(a), Some(b)) => self.segment(&a, &b),
+            _ => self.coda,
+        }
+    }
+
+    pub fn syllables(&self, a: &Syllable, b: &Syllable) -> f64 {
+        self.segment(&a.onset, &b.onset)
+            + self.segment(&a.vowel, &b.vowel)
+            + self.tones(a.tone, b.tone)
+            + self.codas(a.coda, b.coda)
+    }
+
+    pub fn words(&self, a: &Word, b: &Word) -> f64 {
+        self.align(a, b).distance
+    }
+
+    // the cheapest way to turn `a` into `b` a syllable at a time
+    pub fn align(&self, a: &Word, b: &Word) -> Alignment {
+        // cost[i][j] turns the first i syllables of `a` into the first j of `b`
+        let mut cost = vec![vec![0.; b.len() + 1]; a.len() + 1];
+
+        // the edges are built by adding like the rest, so walking back finds the same sums
+        for i in 1..=a.len() {
+            cost[i][0] = cost[i - 1][0] + self.syllable;
+        }
+        for j in 1..=b.len() {
+            cost[0][j] = cost[0][j - 1] + self.syllable;
+        }
+
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                cost[i][j] = (cost[i - 1][j - 1] + self.syllables(&a[i - 1], &b[j - 1]))
+                    .min(cost[i - 1][j] + self.syllable)
+                    .min(cost[i][j - 1] + self.syllable);
+            }
+        }
+
+        // walk back from the end, preferring substitutions
+        let mut edits = Vec::new();
+        let (mut i, mut j) = (a.len(), b.len());
+
+        while i > 0 || j > 0 {
+            if i > 0
+                && j > 0
+                && cost[i][j] == cost[i - 1][j - 1] + self.syllables(&a[i - 1], &b[j - 1])
+            {
+                edits.push(if a[i - 1] == b[j - 1] {
+                    Edit::Keep(a[i - 1])
+                } else {
+                    Edit::Substitute(a[i - 1], b[j - 1])
+                });
+
+                i -= 1;
+                j -= 1;
+            } else if j == 0 || (i > 0 && cost[i][j] == cost[i - 1][j] + self.syllable) {
+                edits.push(Edit::Delete(a[i - 1]));
+                i -= 1;
+            } else {
+                edits.push(Edit::Insert(b[j - 1]));
+                j -= 1;
+            }
+        }
+
+        edits.reverse();
+
+        Alignment {
+            edits,
+            distance: cost[a.len()][b.len()],
+        }
+    }
+
+    // the `k` candidates closest to `query`, closest first. ties keep the candidates' order
+    pub fn nearest<'a, X: Distance + 'a>(
+        &self,
+        query: &X,
+        candidates: impl IntoIterator<Item = &'a X>,
+        k: usize,
+    ) -> Vec<(&'a X, f64)> {
+        let mut nearest: Vec<_> = candidates
+            .into_iter()
+            .map(|candidate| (candidate, query.distance_with(candidate, self)))
+            .collect();
+
+        nearest.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        nearest.truncate(k);
+
+        nearest
+    }
+}
+
+pub trait Distance {
+    fn distance_with(&self, other: &Self, weights: &Weights) -> f64;
+
+    fn distance(&self, other: &Self) -> f64 {
+        self.distance_with(other, Weights::standard())
+    }
+}
+
+macro_rules! impl_segment_distance {
+    ($($ty:ty),*) => {
+        $(
+            impl Distance for $ty {
+                fn distance_with(&self, other: &Self, weights: &Weights) -> f64 {
+                    weights.segment(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_segment_distance!(C, H, V);
+
+impl Distance for T {
+    fn distance_with(&self, other: &Self, weights: &Weights) -> f64 {
+        weights.tones(*self, *other)
+    }
+}
+
+impl Distance for Syllable {
+    fn distance_with(&self, other: &Self, weights: &Weights) -> f64 {
+        weights.syllables(self, other)
+    }
+}
+
+impl Distance for Word {
+    fn distance_with(&self, other: &Self, weights: &Weights) -> f64 {
+        weights.words(self, other)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Edit {
+    Keep(Syllable),
+    Substitute(Syllable, Syllable),
+    Insert(Syllable),
+    Delete(Syllable),
+}
+
+// the syllables of two words paired up, a syllable missing from either side is paired with a gap
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Alignment {
+    pub edits: Vec<Edit>,
+    pub distance: f64,
+}
+
+// two rows with the syllables lined up and `-` for a gap
+impl Display for Alignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<(String, String)> = self
+            .edits
+            .iter()
+            .map(|edit| match edit {
+                Edit::Keep(a) => (a.to_string(), a.to_string()),
+                Edit::Substitute(a, b) => (a.to_string(), b.to_string()),
+                Edit::Insert(b) => ("-".to_owned(), b.to_string()),
+                Edit::Delete(a) => (a.to_string(), "-".to_owned()),
+            })
+            .collect();
+
+        for row in [0, 1] {
+            let mut line = String::new();
+
+            let mut first = true;
+            for (a, b) in &cells {
+                if first {
+                    first = false;
+                } else {
+                    line.push(' ');
+                }
+
+                let width = a.chars().count().max(b.chars().count());
+                let cell = if row == 0 { a } else { b };
+                write!(line, "{cell:<width$}")?;
+            }
+
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        write!(f, "distance {}", self.distance)
+    }
+}
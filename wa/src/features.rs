@@ -0,0 +1,431 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde_derive::*;
+
+use crate::{CStem, PureC, C, H, V};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Place {
+    Bilabial,
+    Labiovelar,
+    Alveolar,
+    Palatal,
+    Velar,
+    Glottal,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Manner {
+    Plosive,
+    Fricative,
+    Trill,
+    Approximant,
+    Lateral,
+    // w and y, which are written as non-syllabic vowels
+    Glide,
+    Vowel,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Major {
+    Obstruent,
+    Sonorant,
+    Vowel,
+}
+
+impl Manner {
+    pub fn major(&self) -> Major {
+        match self {
+            Manner::Plosive | Manner::Fricative => Major::Obstruent,
+            Manner::Trill | Manner::Approximant | Manner::Lateral | Manner::Glide => {
+                Major::Sonorant
+            }
+            Manner::Vowel => Major::Vowel,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Height {
+    Close,
+    Mid,
+    Open,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Backness {
+    Front,
+    Central,
+    Back,
+}
+
+// vowels have no place, consonants have no height or backness except for the glides, which share
+// them with i and u
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Features {
+    pub place: Option<Place>,
+    pub manner: Manner,
+    pub voiced: bool,
+    pub aspirated: bool,
+    pub palatalized: bool,
+    pub height: Option<Height>,
+    pub backness: Option<Backness>,
+    pub rounded: bool,
+}
+
+impl Features {
+    const fn consonant(place: Place, manner: Manner, voiced: bool) -> Self {
+        Self {
+            place: Some(place),
+            manner,
+            voiced,
+            aspirated: false,
+            palatalized: false,
+            height: None,
+            backness: None,
+            rounded: false,
+        }
+    }
+
+    const fn vowel(height: Height, backness: Backness, rounded: bool) -> Self {
+        Self {
+            place: None,
+            manner: Manner::Vowel,
+            voiced: true,
+            aspirated: false,
+            palatalized: false,
+            height: Some(height),
+            backness: Some(backness),
+            rounded,
+        }
+    }
+
+    pub fn major(&self) -> Major {
+        self.manner.major()
+    }
+
+    pub fn is_obstruent(&self) -> bool {
+        self.major() == Major::Obstruent
+    }
+
+    pub fn is_sonorant(&self) -> bool {
+        self.major() != Major::Obstruent
+    }
+
+    pub fn is_glide(&self) -> bool {
+        self.manner == Manner::Glide
+    }
+
+    pub fn is_vowel(&self) -> bool {
+        self.manner == Manner::Vowel
+    }
+}
+
+pub trait Phoneme {
+    fn features(&self) -> Features;
+}
+
+impl Phoneme for PureC {
+    fn features(&self) -> Features {
+        let (stem, strong, blunt, sharp) = match self {
+            PureC::Strong(stem) => (stem, true, false, false),
+            PureC::Blunt(stem) => (stem, false, true, false),
+            PureC::Sharp(stem) => (stem, false, false, true),
+        };
+
+        let place = match stem {
+            CStem::P => Place::Bilabial,
+            CStem::T | CStem::S | CStem::R => Place::Alveolar,
+            CStem::K => Place::Velar,
+        };
+
+        match stem {
+            // strong stops are aspirated, blunt ones voiced and sharp ones palatalised
+            CStem::P | CStem::T | CStem::K => Features {
+                aspirated: strong,
+                palatalized: sharp,
+                ..Features::consonant(place, Manner::Plosive, blunt)
+            },
+            CStem::S => Features {
+                palatalized: sharp,
+                ..Features::consonant(place, Manner::Fricative, blunt)
+            },
+            // r, ɹ and l
+            CStem::R => {
+                let manner = match self {
+                    PureC::Strong(_) => Manner::Trill,
+                    PureC::Blunt(_) => Manner::Approximant,
+                    PureC::Sharp(_) => Manner::Lateral,
+                };
+
+                Features::consonant(place, manner, true)
+            }
+        }
+    }
+}
+
+impl Phoneme for H {
+    fn features(&self) -> Features {
+        match self {
+            H::W => Features {
+                height: Some(Height::Close),
+                backness: Some(Backness::Back),
+                rounded: true,
+                ..Features::consonant(Place::Labiovelar, Manner::Glide, true)
+            },
+            H::Y => Features {
+                height: Some(Height::Close),
+                backness: Some(Backness::Front),
+                ..Features::consonant(Place::Palatal, Manner::Glide, true)
+            },
+            H::X => Features::consonant(Place::Velar, Manner::Fricative, false),
+            H::H => Features::consonant(Place::Glottal, Manner::Fricative, false),
+        }
+    }
+}
+
+impl Phoneme for C {
+    fn features(&self) -> Features {
+        match self {
+            C::H(h) => h.features(),
+            C::C(c) => c.features(),
+        }
+    }
+}
+
+impl Phoneme for V {
+    fn features(&self) -> Features {
+        match self {
+            V::A => Features::vowel(Height::Open, Backness::Central, false),
+            V::E => Features::vowel(Height::Mid, Backness::Front, false),
+            V::I => Features::vowel(Height::Close, Backness::Front, false),
+            V::O => Features::vowel(Height::Mid, Backness::Back, true),
+            V::U => Features::vowel(Height::Close, Backness::Back, true),
+        }
+    }
+}
+
+// a consonant or a vowel, codas are the consonants in `H`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Segment {
+    Consonant(C),
+    Vowel(V),
+}
+
+impl Segment {
+    pub const COUNT: usize = C::COUNT + V::COUNT;
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        C::all()
+            .map(Segment::Consonant)
+            .chain(V::all().map(Segment::Vowel))
+    }
+}
+
+impl Display for Segment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Segment::Consonant(c) => c.fmt(f),
+            Segment::Vowel(v) => v.fmt(f),
+        }
+    }
+}
+
+impl Phoneme for Segment {
+    fn features(&self) -> Features {
+        match self {
+            Segment::Consonant(c) => c.features(),
+            Segment::Vowel(v) => v.features(),
+        }
+    }
+}
+
+// a natural class, every feature that is set has to match. classes can be narrowed with struct
+// update syntax, `Class { palatalized: Some(true), ..Class::OBSTRUENTS }` is the sharp obstruents
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Class {
+    pub major: Option<Major>,
+    pub place: Option<Place>,
+    pub manner: Option<Manner>,
+    pub voiced: Option<bool>,
+    pub aspirated: Option<bool>,
+    pub palatalized: Option<bool>,
+    pub height: Option<Height>,
+    pub backness: Option<Backness>,
+    pub rounded: Option<bool>,
+}
+
+impl Class {
+    pub const ANY: Class = Class {
+        major: None,
+        place: None,
+        manner: None,
+        voiced: None,
+        aspirated: None,
+        palatalized: None,
+        height: None,
+        backness: None,
+        rounded: None,
+    };
+
+    pub const OBSTRUENTS: Class = Class {
+        major: Some(Major::Obstruent),
+        ..Class::ANY
+    };
+
+    pub const SONORANTS: Class = Class {
+        major: Some(Major::Sonorant),
+        ..Class::ANY
+    };
+
+    pub const VOWELS: Class = Class {
+        major: Some(Major::Vowel),
+        ..Class::ANY
+    };
+
+    pub const PLOSIVES: Class = Class {
+        manner: Some(Manner::Plosive),
+        ..Class::ANY
+    };
+
+    pub const FRICATIVES: Class = Class {
+        manner: Some(Manner::Fricative),
+        ..Class::ANY
+    };
+
+    pub const GLIDES: Class = Class {
+        manner: Some(Manner::Glide),
+        ..Class::ANY
+    };
+
+    pub const ASPIRATED: Class = Class {
+        aspirated: Some(true),
+        ..Class::ANY
+    };
+
+    pub const VOICED: Class = Class {
+        voiced: Some(true),
+        ..Class::ANY
+    };
+
+    // the sharp series of `PureC`, except for l which isn't palatalised
+    pub const PALATALIZED: Class = Class {
+        palatalized: Some(true),
+        ..Class::ANY
+    };
+
+    pub fn matches(&self, features: &Features) -> bool {
+        fn check<X: PartialEq>(wanted: Option<X>, actual: X) -> bool {
+            wanted.is_none_or(|wanted| wanted == actual)
+        }
+
+        // a class with a place or a vowel quality never matches segments without one
+        fn check_some<X: PartialEq>(wanted: Option<X>, actual: Option<X>) -> bool {
+            wanted.is_none() || wanted == actual
+        }
+
+        check(self.major, features.major())
+            && check_some(self.place, features.place)
+            && check(self.manner, features.manner)
+            && check(self.voiced, features.voiced)
+            && check(self.aspirated, features.aspirated)
+            && check(self.palatalized, features.palatalized)
+            && check_some(self.height, features.height)
+            && check_some(self.backness, features.backness)
+            && check(self.rounded, features.rounded)
+    }
+
+    pub fn contains(&self, phoneme: &impl Phoneme) -> bool {
+        self.matches(&phoneme.features())
+    }
+
+    pub fn onsets(&self) -> Vec<C> {
+        C::all().filter(|c| self.contains(c)).collect()
+    }
+
+    pub fn codas(&self) -> Vec<H> {
+        H::all().filter(|h| self.contains(h)).collect()
+    }
+
+    pub fn vowels(&self) -> Vec<V> {
+        V::all().filter(|v| self.contains(v)).collect()
+    }
+
+    pub fn segments(&self) -> Vec<Segment> {
+        Segment::all().filter(|s| self.contains(s)).collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownFeature(pub String);
+
+impl Display for UnknownFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown feature '{}'", self.0)
+    }
+}
+
+impl std::error::Error for UnknownFeature {}
+
+// features separated by spaces, like `plosive -voiced +palatalized`. places, manners, heights,
+// backnesses, obstruent and sonorant are named, the rest are written with + or -
+impl FromStr for Class {
+    type Err = UnknownFeature;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut class = Class::ANY;
+
+        for feature in s.split_whitespace() {
+            let unknown = || UnknownFeature(feature.to_owned());
+
+            if let Some((sign, name)) = feature
+                .strip_prefix('+')
+                .map(|name| (true, name))
+                .or_else(|| feature.strip_prefix('-').map(|name| (false, name)))
+            {
+                match name {
+                    "voiced" => class.voiced = Some(sign),
+                    "aspirated" => class.aspirated = Some(sign),
+                    "palatalized" => class.palatalized = Some(sign),
+                    "rounded" => class.rounded = Some(sign),
+                    _ => return Err(unknown()),
+                }
+
+                continue;
+            }
+
+            match feature {
+                "obstruent" => class.major = Some(Major::Obstruent),
+                "sonorant" => class.major = Some(Major::Sonorant),
+
+                "bilabial" => class.place = Some(Place::Bilabial),
+                "labiovelar" => class.place = Some(Place::Labiovelar),
+                "alveolar" => class.place = Some(Place::Alveolar),
+                "palatal" => class.place = Some(Place::Palatal),
+                "velar" => class.place = Some(Place::Velar),
+                "glottal" => class.place = Some(Place::Glottal),
+
+                "plosive" => class.manner = Some(Manner::Plosive),
+                "fricative" => class.manner = Some(Manner::Fricative),
+                "trill" => class.manner = Some(Manner::Trill),
+                "approximant" => class.manner = Some(Manner::Approximant),
+                "lateral" => class.manner = Some(Manner::Lateral),
+                "glide" => class.manner = Some(Manner::Glide),
+                "vowel" => class.manner = Some(Manner::Vowel),
+
+                "close" => class.height = Some(Height::Close),
+                "mid" => class.height = Some(Height::Mid),
+                "open" => class.height = Some(Height::Open),
+
+                "front" => class.backness = Some(Backness::Front),
+                "central" => class.backness = Some(Backness::Central),
+                "back" => class.backness = Some(Backness::Back),
+
+                _ => return Err(unknown()),
+            }
+        }
+
+        Ok(class)
+    }
+}
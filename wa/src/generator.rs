@@ -0,0 +1,459 @@
+use std::{fmt::Display, sync::OnceLock};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde_derive::*;
+
+use crate::{
+    phonotactics::Phonotactics, CStem, Paragraph, Phrase, Punctuation, PureC, Sentence, Syllable,
+    Text, Word, C, H, T, V,
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Length {
+    // after n items there is a 1/(n + 1) chance of another one
+    Harmonic,
+    Fixed(usize),
+    // both ends are inclusive
+    Uniform { min: usize, max: usize },
+    // the weight of a length of n is at index n - 1
+    Weights(Vec<f64>),
+}
+
+impl Length {
+    pub fn sample(&self, rng: &mut impl Rng) -> usize {
+        match self {
+            Length::Harmonic => {
+                let mut length = 0;
+
+                let mut n = 1.;
+
+                while rng.gen_bool(1. / n) {
+                    length += 1;
+                    n += 1.;
+                }
+
+                length
+            }
+            Length::Fixed(length) => *length,
+            Length::Uniform { min, max } => rng.gen_range(*min..=*max),
+            Length::Weights(weights) => {
+                let lengths = weights
+                    .iter()
+                    .enumerate()
+                    .map(|(i, weight)| (i + 1, *weight))
+                    .collect::<Vec<_>>();
+
+                pick(rng, &lengths)
+            }
+        }
+    }
+
+    // whether a sample can be more than `n`
+    fn can_exceed(&self, n: usize) -> bool {
+        match self {
+            Length::Harmonic => true,
+            Length::Fixed(length) => *length > n,
+            Length::Uniform { max, .. } => *max > n,
+            Length::Weights(weights) => weights.iter().skip(n).any(|weight| *weight > 0.),
+        }
+    }
+
+    fn validate(&self, name: &'static str) -> Result<(), ConfigError> {
+        match self {
+            Length::Uniform { min, max } if min > max => Err(ConfigError::Range(name)),
+            Length::Weights(weights) => validate_weights(name, weights.iter().copied()),
+            _ => Ok(()),
+        }
+    }
+}
+
+// every weight is relative to the others in its list, a missing entry is never picked
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneratorConfig {
+    // without a seed the generator is seeded from the OS
+    pub seed: Option<u64>,
+
+    pub onsets: Vec<(C, f64)>,
+    pub vowels: Vec<(V, f64)>,
+    pub tones: Vec<(T, f64)>,
+    pub codas: Vec<(Option<H>, f64)>,
+    pub punctuation: Vec<(Punctuation, f64)>,
+
+    pub word_length: Length,
+    pub phrase_length: Length,
+    pub sentence_length: Length,
+    pub paragraph_length: Length,
+    pub text_length: Length,
+
+    // syllables that break these rules are drawn again
+    pub phonotactics: Phonotactics,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        // 4/15 of onsets are H
+        let onsets = C::all()
+            .map(|c| match c {
+                C::H(_) => (c, 15.),
+                C::C(_) => (c, 11.),
+            })
+            .collect();
+
+        // 4/5 of syllables have a coda
+        let codas = std::iter::once((None, 1.))
+            .chain(H::all().map(|h| (Some(h), 1.)))
+            .collect();
+
+        Self {
+            seed: None,
+
+            onsets,
+            vowels: V::all().map(|v| (v, 1.)).collect(),
+            tones: T::all().map(|t| (t, 1.)).collect(),
+            codas,
+            punctuation: vec![
+                (Punctuation::WordBreak, 4.),
+                (Punctuation::PhraseBreak, 3.),
+                (Punctuation::SentenceBreak, 3.),
+            ],
+
+            word_length: Length::Harmonic,
+            phrase_length: Length::Harmonic,
+            sentence_length: Length::Harmonic,
+            paragraph_length: Length::Harmonic,
+            text_length: Length::Harmonic,
+
+            phonotactics: Phonotactics::default(),
+        }
+    }
+}
+
+impl GeneratorConfig {
+    // the configuration behind the `Random` impls
+    pub fn standard() -> &'static GeneratorConfig {
+        static STANDARD: OnceLock<GeneratorConfig> = OnceLock::new();
+
+        STANDARD.get_or_init(GeneratorConfig::default)
+    }
+
+    // what would make generating panic or never finish, `Generator::new` checks this
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        fn weights<X>(list: &[(X, f64)]) -> impl Iterator<Item = f64> + '_ {
+            list.iter().map(|(_, weight)| *weight)
+        }
+
+        validate_weights("onsets", weights(&self.onsets))?;
+        validate_weights("vowels", weights(&self.vowels))?;
+        validate_weights("tones", weights(&self.tones))?;
+        validate_weights("codas", weights(&self.codas))?;
+        validate_weights("punctuation", weights(&self.punctuation))?;
+
+        self.word_length.validate("word_length")?;
+        self.phrase_length.validate("phrase_length")?;
+        self.sentence_length.validate("sentence_length")?;
+        self.paragraph_length.validate("paragraph_length")?;
+        self.text_length.validate("text_length")?;
+
+        // any syllable that can start a word can come before another one, so when words can be
+        // longer than one syllable every one of them needs something to follow it
+        let candidates: Vec<Syllable> = self.candidates().map(|(syllable, _)| syllable).collect();
+        let starts: Vec<&Syllable> = candidates
+            .iter()
+            .filter(|syllable| self.phonotactics.allows(None, syllable))
+            .collect();
+
+        if starts.is_empty() {
+            return Err(ConfigError::NoSyllable(None));
+        }
+
+        if self.word_length.can_exceed(1) {
+            for previous in starts {
+                let followed = candidates
+                    .iter()
+                    .any(|next| self.phonotactics.allows(Some(previous), next));
+
+                if !followed {
+                    return Err(ConfigError::NoSyllable(Some(*previous)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // every syllable the weights can give, with its weight
+    fn candidates(&self) -> impl Iterator<Item = (Syllable, f64)> + '_ {
+        fn positive<X: Copy>(list: &[(X, f64)]) -> impl Iterator<Item = (X, f64)> + Clone + '_ {
+            list.iter().copied().filter(|(_, weight)| *weight > 0.)
+        }
+
+        positive(&self.onsets).flat_map(move |(onset, a)| {
+            positive(&self.vowels).flat_map(move |(vowel, b)| {
+                positive(&self.tones).flat_map(move |(tone, c)| {
+                    positive(&self.codas).map(move |(coda, d)| {
+                        let syllable = Syllable {
+                            onset,
+                            vowel,
+                            tone,
+                            coda,
+                        };
+
+                        (syllable, a * b * c * d)
+                    })
+                })
+            })
+        })
+    }
+
+    pub fn onset(&self, rng: &mut impl Rng) -> C {
+        pick(rng, &self.onsets)
+    }
+
+    pub fn vowel(&self, rng: &mut impl Rng) -> V {
+        pick(rng, &self.vowels)
+    }
+
+    pub fn tone(&self, rng: &mut impl Rng) -> T {
+        pick(rng, &self.tones)
+    }
+
+    pub fn coda(&self, rng: &mut impl Rng) -> Option<H> {
+        pick(rng, &self.codas)
+    }
+
+    // the onsets that aren't H, with their weights
+    pub fn consonant(&self, rng: &mut impl Rng) -> PureC {
+        let consonants: Vec<(PureC, f64)> = self
+            .onsets
+            .iter()
+            .filter_map(|(onset, weight)| match onset {
+                C::C(c) => Some((*c, *weight)),
+                C::H(_) => None,
+            })
+            .collect();
+
+        pick(rng, &consonants)
+    }
+
+    pub fn stem(&self, rng: &mut impl Rng) -> CStem {
+        self.consonant(rng).stem()
+    }
+
+    // the codas that aren't empty, with their weights
+    pub fn h(&self, rng: &mut impl Rng) -> H {
+        let hs: Vec<(H, f64)> = self
+            .codas
+            .iter()
+            .filter_map(|(coda, weight)| coda.map(|h| (h, *weight)))
+            .collect();
+
+        pick(rng, &hs)
+    }
+
+    pub fn punctuation(&self, rng: &mut impl Rng) -> Punctuation {
+        pick(rng, &self.punctuation)
+    }
+
+    pub fn syllable(&self, rng: &mut impl Rng) -> Syllable {
+        self.syllable_after(rng, None)
+    }
+
+    // drawing again until the phonotactics allow it is fast when they forbid little, after a while
+    // the syllable is drawn from only the permitted ones instead
+    fn syllable_after(&self, rng: &mut impl Rng, previous: Option<&Syllable>) -> Syllable {
+        const ATTEMPTS: usize = 1_000;
+
+        for _ in 0..ATTEMPTS {
+            let syllable = Syllable {
+                onset: self.onset(rng),
+                vowel: self.vowel(rng),
+                tone: self.tone(rng),
+                coda: self.coda(rng),
+            };
+
+            if self.phonotactics.allows(previous, &syllable) {
+                return syllable;
+            }
+        }
+
+        let permitted: Vec<(Syllable, f64)> = self
+            .candidates()
+            .filter(|(syllable, _)| self.phonotactics.allows(previous, syllable))
+            .collect();
+
+        pick(rng, &permitted)
+    }
+
+    pub fn word(&self, rng: &mut impl Rng) -> Word {
+        let length = self.word_length.sample(rng);
+
+        let mut word = Vec::with_capacity(length);
+
+        for _ in 0..length {
+            let syllable = self.syllable_after(rng, word.last());
+            word.push(syllable);
+        }
+
+        Word(word)
+    }
+
+    pub fn phrase(&self, rng: &mut impl Rng) -> Phrase {
+        let length = self.phrase_length.sample(rng);
+
+        Phrase((0..length).map(|_| self.word(rng)).collect())
+    }
+
+    pub fn sentence(&self, rng: &mut impl Rng) -> Sentence {
+        let length = self.sentence_length.sample(rng);
+
+        Sentence::new((0..length).map(|_| self.phrase(rng)).collect())
+    }
+
+    pub fn paragraph(&self, rng: &mut impl Rng) -> Paragraph {
+        let length = self.paragraph_length.sample(rng);
+
+        Paragraph((0..length).map(|_| self.sentence(rng)).collect())
+    }
+
+    pub fn text(&self, rng: &mut impl Rng) -> Text {
+        let length = self.text_length.sample(rng);
+
+        Text((0..length).map(|_| self.paragraph(rng)).collect())
+    }
+}
+
+// a generator owns its rng, so the same seed and configuration always produce the same output
+pub struct Generator {
+    config: GeneratorConfig,
+    rng: StdRng,
+}
+
+impl Generator {
+    pub fn new(config: GeneratorConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Ok(Self { config, rng })
+    }
+
+    pub fn config(&self) -> &GeneratorConfig {
+        &self.config
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    pub fn syllable(&mut self) -> Syllable {
+        self.config.syllable(&mut self.rng)
+    }
+
+    pub fn word(&mut self) -> Word {
+        self.config.word(&mut self.rng)
+    }
+
+    pub fn phrase(&mut self) -> Phrase {
+        self.config.phrase(&mut self.rng)
+    }
+
+    pub fn sentence(&mut self) -> Sentence {
+        self.config.sentence(&mut self.rng)
+    }
+
+    pub fn paragraph(&mut self) -> Paragraph {
+        self.config.paragraph(&mut self.rng)
+    }
+
+    pub fn text(&mut self) -> Text {
+        self.config.text(&mut self.rng)
+    }
+
+    pub fn punctuation(&mut self) -> Punctuation {
+        self.config.punctuation(&mut self.rng)
+    }
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self::new(GeneratorConfig::default()).expect("the default configuration is valid")
+    }
+}
+
+// what `GeneratorConfig::validate` finds, the names are the config's fields
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConfigError {
+    // the weights are all 0 or less, or add up to infinity
+    Weights(&'static str),
+    // a uniform length with its min above its max
+    Range(&'static str),
+    // the phonotactics forbid every syllable the weights can give, after this one if there is one
+    NoSyllable(Option<Syllable>),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Weights(name) => {
+                write!(f, "the weights of {name} must add up to a number above 0")
+            }
+            ConfigError::Range(name) => write!(f, "the min of {name} is above its max"),
+            ConfigError::NoSyllable(None) => {
+                write!(
+                    f,
+                    "the phonotactics forbid every syllable that can be generated"
+                )
+            }
+            ConfigError::NoSyllable(Some(previous)) => write!(
+                f,
+                "the phonotactics forbid every syllable that can be generated after {previous}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn validate_weights(
+    name: &'static str,
+    weights: impl Iterator<Item = f64>,
+) -> Result<(), ConfigError> {
+    let total: f64 = weights.map(|weight| weight.max(0.)).sum();
+
+    if total > 0. && total.is_finite() {
+        Ok(())
+    } else {
+        Err(ConfigError::Weights(name))
+    }
+}
+
+// the weights must add up to a number above 0
+pub(crate) fn pick<X: Copy>(rng: &mut impl Rng, weights: &[(X, f64)]) -> X {
+    let total: f64 = weights.iter().map(|(_, weight)| weight.max(0.)).sum();
+
+    assert!(total > 0., "nothing to pick from, every weight is zero");
+
+    let mut target = rng.gen_range(0. ..total);
+
+    for (x, weight) in weights {
+        let weight = weight.max(0.);
+
+        if target < weight {
+            return *x;
+        }
+
+        target -= weight;
+    }
+
+    // rounding can leave a sliver at the end
+    weights
+        .iter()
+        .rev()
+        .find(|(_, weight)| *weight > 0.)
+        .map(|(x, _)| *x)
+        .unwrap()
+}
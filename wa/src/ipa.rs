@@ -0,0 +1,190 @@
+use unicode_normalization::char::is_combining_mark;
+
+use crate::sandhi::Sandhi;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Detail {
+    // phonemic, without sandhi or allophonic detail
+    Broad,
+    // phonetic, with the sandhi rules applied
+    #[default]
+    Narrow,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ToneStyle {
+    // ˧˥˧
+    #[default]
+    Letters,
+    // ³⁵³, a level tone is written twice like ⁵⁵
+    Numbers,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IpaOptions {
+    pub detail: Detail,
+    pub tones: ToneStyle,
+    // only ASCII characters, see `ASCII`. tones are always numbers
+    pub ascii: bool,
+    // broad transcriptions in /slashes/ and narrow ones in [brackets]
+    pub delimit: bool,
+    // a nasal tone on its own with its pitch after the nasalisation
+    pub nasal_pitch: bool,
+    // the rules of a narrow transcription, the standard ones if there are none
+    pub sandhi: Option<Sandhi>,
+}
+
+impl IpaOptions {
+    // what `Ipa::ipa` writes
+    pub fn narrow() -> Self {
+        Self::default()
+    }
+
+    pub fn broad() -> Self {
+        Self {
+            detail: Detail::Broad,
+            ..Self::default()
+        }
+    }
+
+    pub fn sandhi(&self) -> &Sandhi {
+        static NONE: Sandhi = Sandhi { rules: Vec::new() };
+
+        match self.detail {
+            Detail::Broad => &NONE,
+            Detail::Narrow => self.sandhi.as_ref().unwrap_or(Sandhi::standard()),
+        }
+    }
+
+    // restyles a narrow transcription
+    pub fn format(&self, ipa: &str) -> String {
+        let mut ipa = ipa.to_owned();
+
+        if self.detail == Detail::Broad {
+            ipa = replace_all(&replace_all(&ipa, GLIDES), BROAD);
+        }
+
+        if self.tones == ToneStyle::Numbers || self.ascii {
+            ipa = tone_numbers(&ipa, self.ascii);
+        }
+
+        if self.ascii {
+            ipa = ascii(&ipa);
+        }
+
+        self.delimited(&ipa)
+    }
+
+    pub(crate) fn delimited(&self, s: &str) -> String {
+        if !self.delimit {
+            return s.to_owned();
+        }
+
+        let (open, close) = match self.detail {
+            Detail::Broad => ('/', '/'),
+            Detail::Narrow => ('[', ']'),
+        };
+
+        delimit(s, open, close)
+    }
+}
+
+const GLIDES: &[(&str, &str)] = &[("u\u{032F}\u{0357}", "w"), ("i\u{032F}\u{0351}", "j")];
+
+const BROAD: &[(&str, &str)] = &[
+    ("ä", "a"),
+    // lowered
+    ("\u{031E}", ""),
+    // apical
+    ("\u{033A}", ""),
+];
+
+// the IPA with the closest ASCII letter for each symbol, and ɹ as the capital R. the other
+// diacritics are dropped, including the unreleased mark since an h before a plosive is always
+// unreleased. a question's global rise is '?' and an exclamation's global fall is '!'
+const ASCII: &[(&str, &str)] = &[
+    ("ä", "a"),
+    ("ʰ", "h"),
+    ("ʲ", "j"),
+    ("ɹ", "R"),
+    ("ː", ":"),
+    ("\u{0303}", "~"),
+    ("↗", "?"),
+    ("↘", "!"),
+    // both quotation marks, one right after a word closes
+    ("“", "\""),
+    ("”", "\""),
+];
+
+fn replace_all(s: &str, replacements: &[(&str, &str)]) -> String {
+    replacements
+        .iter()
+        .fold(s.to_owned(), |s, (from, to)| s.replace(from, to))
+}
+
+const TONE_LETTERS: [char; 5] = ['˩', '˨', '˧', '˦', '˥'];
+
+fn tone_numbers(s: &str, ascii: bool) -> String {
+    let number = |level: usize| {
+        if ascii {
+            char::from(b'1' + level as u8)
+        } else {
+            ['¹', '²', '³', '⁴', '⁵'][level]
+        }
+    };
+
+    let mut buffer = String::new();
+    let mut run = Vec::new();
+
+    let flush = |run: &mut Vec<usize>, buffer: &mut String| {
+        if run.len() == 1 {
+            run.push(run[0]);
+        }
+
+        buffer.extend(run.drain(..).map(number));
+    };
+
+    for ch in s.chars() {
+        match TONE_LETTERS.iter().position(|letter| *letter == ch) {
+            Some(level) => run.push(level),
+            None => {
+                flush(&mut run, &mut buffer);
+                buffer.push(ch);
+            }
+        }
+    }
+
+    flush(&mut run, &mut buffer);
+
+    buffer
+}
+
+// anything without an ASCII spelling is dropped if it's a diacritic and replaced with '*' otherwise
+fn ascii(s: &str) -> String {
+    replace_all(&replace_all(s, GLIDES), ASCII)
+        .chars()
+        .filter(|ch| !is_combining_mark(*ch))
+        .map(|ch| if ch.is_ascii() { ch } else { '*' })
+        .collect()
+}
+
+// each line is delimited on its own
+fn delimit(s: &str, open: char, close: char) -> String {
+    let mut buffer = String::new();
+
+    for line in s.split_inclusive('\n') {
+        let content = line.trim_end();
+
+        if !content.is_empty() {
+            buffer.push(open);
+            buffer.push_str(content);
+            buffer.push(close);
+        }
+
+        if line.ends_with('\n') {
+            buffer.push('\n');
+        }
+    }
+
+    buffer
+}
@@ -0,0 +1,547 @@
+use unicode_normalization::char::decompose_canonical;
+
+use crate::{
+    CStem, Enclosure, Expected, Paragraph, ParseError, Path, Phrase, PureC, Sentence, Separator,
+    Span, Syllable, Terminator, Text, Word, C, H, T, V,
+};
+
+// reads back what `Ipa` writes, broad or narrow, including what the standard sandhi rules do to
+// it. looser spellings are also understood: diacritics that don't tell phonemes apart can be left
+// out, glides can be w and j, aspiration and palatalisation can be h and j, ɹ can be R, tones can
+// be Chao numbers and the whole transcription can be in /slashes/ or [brackets]
+pub trait FromIpa: Sized {
+    fn from_ipa(ipa: &str) -> Result<Self, ParseError>;
+}
+
+pub fn from_ipa<L: FromIpa>(ipa: &str) -> Result<L, ParseError> {
+    L::from_ipa(ipa)
+}
+
+macro_rules! impl_from_ipa {
+    ($ty:ty, $node:expr) => {
+        impl FromIpa for $ty {
+            fn from_ipa(ipa: &str) -> Result<Self, ParseError> {
+                IpaParser::new(ipa).parse($node)
+            }
+        }
+    };
+}
+
+impl_from_ipa!(Syllable, |parser| parser.syllable().map(|(x, _)| x));
+impl_from_ipa!(Word, |parser| parser.word().map(|(x, _)| x));
+impl_from_ipa!(Phrase, |parser| parser.phrase().map(|(x, _)| x));
+impl_from_ipa!(Sentence, IpaParser::sentence);
+impl_from_ipa!(Paragraph, IpaParser::paragraph);
+impl_from_ipa!(Text, IpaParser::text);
+
+const IGNORED: &[char] = &[
+    '\u{0308}', // the diaeresis of ä
+    '\u{031E}', // lowered
+    '\u{033A}', // apical
+    '\u{032F}', // non-syllabic
+    '\u{0357}', // more rounded
+    '\u{0351}', // less rounded
+    '\u{031A}', // unreleased
+];
+
+const ONSETS: &[(&str, C)] = &[
+    ("pʰ", C::C(PureC::Strong(CStem::P))),
+    ("ph", C::C(PureC::Strong(CStem::P))),
+    ("p", C::C(PureC::Strong(CStem::P))),
+    ("b", C::C(PureC::Blunt(CStem::P))),
+    ("pʲ", C::C(PureC::Sharp(CStem::P))),
+    ("pj", C::C(PureC::Sharp(CStem::P))),
+    ("tʰ", C::C(PureC::Strong(CStem::T))),
+    ("th", C::C(PureC::Strong(CStem::T))),
+    ("t", C::C(PureC::Strong(CStem::T))),
+    ("d", C::C(PureC::Blunt(CStem::T))),
+    ("tʲ", C::C(PureC::Sharp(CStem::T))),
+    ("tj", C::C(PureC::Sharp(CStem::T))),
+    ("kʰ", C::C(PureC::Strong(CStem::K))),
+    ("kh", C::C(PureC::Strong(CStem::K))),
+    ("k", C::C(PureC::Strong(CStem::K))),
+    ("g", C::C(PureC::Blunt(CStem::K))),
+    ("ɡ", C::C(PureC::Blunt(CStem::K))),
+    ("kʲ", C::C(PureC::Sharp(CStem::K))),
+    ("kj", C::C(PureC::Sharp(CStem::K))),
+    ("s", C::C(PureC::Strong(CStem::S))),
+    ("z", C::C(PureC::Blunt(CStem::S))),
+    ("sʲ", C::C(PureC::Sharp(CStem::S))),
+    ("sj", C::C(PureC::Sharp(CStem::S))),
+    ("r", C::C(PureC::Strong(CStem::R))),
+    ("ɹ", C::C(PureC::Blunt(CStem::R))),
+    ("R", C::C(PureC::Blunt(CStem::R))),
+    ("l", C::C(PureC::Sharp(CStem::R))),
+    ("u", C::H(H::W)),
+    ("w", C::H(H::W)),
+    ("i", C::H(H::Y)),
+    ("j", C::H(H::Y)),
+    ("x", C::H(H::X)),
+    ("h", C::H(H::H)),
+];
+
+const VOWELS: &[(&str, V)] = &[
+    ("a", V::A),
+    ("e", V::E),
+    ("i", V::I),
+    ("o", V::O),
+    ("u", V::U),
+];
+
+// an h coda is an unreleased stop before a plosive
+const CODAS: &[(&str, H)] = &[
+    ("u", H::W),
+    ("w", H::W),
+    ("i", H::Y),
+    ("j", H::Y),
+    ("x", H::X),
+    ("h", H::H),
+    ("p", H::H),
+    ("t", H::H),
+    ("k", H::H),
+];
+
+const SEPARATORS: &[(&str, Separator)] = &[(",", Separator::Phrase), ("|", Separator::List)];
+
+// statements aren't marked. '?' and '!' are the ASCII spellings
+const TERMINATORS: &[(&str, Terminator)] = &[
+    ("↗", Terminator::Question),
+    ("?", Terminator::Question),
+    ("↘", Terminator::Exclamation),
+    ("!", Terminator::Exclamation),
+];
+
+// “quotations” and {parentheticals}. a '"' right after a word closes and anywhere else it opens,
+// the way the ASCII transcription writes both quotation marks
+const OPENING: &[(&str, Enclosure)] = &[
+    ("“", Enclosure::Quotation),
+    ("\"", Enclosure::Quotation),
+    ("{", Enclosure::Parenthetical),
+];
+
+const CLOSING: &[(&str, Enclosure)] = &[
+    ("”", Enclosure::Quotation),
+    ("\"", Enclosure::Quotation),
+    ("}", Enclosure::Parenthetical),
+];
+
+fn tone_level(ch: char) -> Option<u8> {
+    let level = match ch {
+        '˩' | '¹' | '1' => 1,
+        '˨' | '²' | '2' => 2,
+        '˧' | '³' | '3' => 3,
+        '˦' | '⁴' | '4' => 4,
+        '˥' | '⁵' | '5' => 5,
+        _ => return None,
+    };
+
+    Some(level)
+}
+
+struct IpaParser<'a> {
+    src: &'a str,
+    // decomposed, without the ignored diacritics, each with the offset of the character it came from
+    chars: Vec<(usize, char)>,
+    pos: usize,
+    path: Path,
+    // only while parsing a sentence, phrases and words can't have spans
+    enclosures: Option<Enclosures>,
+}
+
+// the spans of the sentence so far, ordered by where they open
+#[derive(Default)]
+struct Enclosures {
+    spans: Vec<Span>,
+    // indices into `spans`, the innermost last
+    open: Vec<usize>,
+    words: usize,
+}
+
+impl<'a> IpaParser<'a> {
+    fn new(src: &'a str) -> Self {
+        let mut chars = Vec::new();
+
+        for (offset, ch) in src.char_indices() {
+            decompose_canonical(ch, |ch| {
+                if !IGNORED.contains(&ch) {
+                    chars.push((offset, ch));
+                }
+            });
+        }
+
+        Self {
+            src,
+            chars,
+            pos: 0,
+            path: Path::default(),
+            enclosures: None,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, n: usize) -> Option<char> {
+        self.chars.get(self.pos + n).map(|(_, ch)| *ch)
+    }
+
+    fn offset(&self, pos: usize) -> usize {
+        self.chars
+            .get(pos)
+            .map_or(self.src.len(), |(offset, _)| *offset)
+    }
+
+    fn error_at(&self, start: usize, expected: Expected) -> ParseError {
+        let end = self.offset(self.pos.max(start + 1));
+        let found = self
+            .chars
+            .get(start)
+            .map(|(offset, _)| self.src[*offset..].chars().next().unwrap_or_default());
+
+        let mut error = ParseError::new(self.offset(start)..end, expected, found);
+        error.path = self.path;
+        error
+    }
+
+    fn error(&self, expected: Expected) -> ParseError {
+        self.error_at(self.pos, expected)
+    }
+
+    // the longest entry of `table` at the current position
+    fn take<X: Copy>(&mut self, table: &[(&str, X)]) -> Option<X> {
+        let (length, x) = table
+            .iter()
+            .filter_map(|(spelling, x)| {
+                let length = spelling.chars().count();
+                let matches = spelling
+                    .chars()
+                    .enumerate()
+                    .all(|(i, ch)| self.peek_at(i) == Some(ch));
+
+                matches.then_some((length, *x))
+            })
+            .max_by_key(|(length, _)| *length)?;
+
+        self.pos += length;
+        Some(x)
+    }
+
+    fn eat(&mut self, chars: &[char]) -> bool {
+        match self.peek() {
+            Some(ch) if chars.contains(&ch) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while self
+            .peek()
+            .is_some_and(|ch| ch.is_whitespace() && ch != '\n')
+        {
+            self.pos += 1;
+        }
+    }
+
+    // delimiters are blank too, so each line can be in its own
+    fn skip_blank(&mut self) {
+        while self
+            .peek()
+            .is_some_and(|ch| ch.is_whitespace() || is_delimiter(ch))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn parse<N>(
+        mut self,
+        node: impl FnOnce(&mut Self) -> Result<N, ParseError>,
+    ) -> Result<N, ParseError> {
+        self.skip_blank();
+        let node = node(&mut self)?;
+        self.skip_blank();
+
+        match self.peek() {
+            None => Ok(node),
+            Some(_) => Err(self.error(Expected::EndOfInput)),
+        }
+    }
+
+    // a syllable and whether its onset is long, which is what a glide coda merging into the same
+    // glide leaves behind
+    fn syllable(&mut self) -> Result<(Syllable, bool), ParseError> {
+        let onset = self
+            .take(ONSETS)
+            .ok_or_else(|| self.error(Expected::Onset))?;
+        let long = self.eat(&['ː', ':']);
+
+        let vowel = self
+            .take(VOWELS)
+            .ok_or_else(|| self.error(Expected::Vowel))?;
+        let nasal = self.eat(&['\u{0303}', '~']);
+
+        let coda = self.take(CODAS);
+
+        let start = self.pos;
+        let mut levels = Vec::new();
+        while let Some(level) = self.peek().and_then(tone_level) {
+            levels.push(level);
+            self.pos += 1;
+        }
+        levels.dedup();
+
+        let tone = match levels[..] {
+            _ if nasal && !levels.is_empty() => T::Nasal,
+            [5] => T::High,
+            [1] => T::Low,
+            // a peaking tone before another one only rises
+            [3, 5, 3] | [3, 5] => T::Peaking,
+            _ => return Err(self.error_at(start, Expected::Tone)),
+        };
+
+        let syllable = Syllable {
+            onset,
+            vowel,
+            tone,
+            coda,
+        };
+
+        Ok((syllable, long))
+    }
+
+    // a syllable follows a period inside a word, anything else ends the sentence
+    fn at_syllable_break(&self) -> bool {
+        self.peek() == Some('.')
+            && self
+                .peek_at(1)
+                .is_some_and(|ch| !ch.is_whitespace() && !is_delimiter(ch) && !is_punctuation(ch))
+    }
+
+    fn word(&mut self) -> Result<(Word, bool), ParseError> {
+        let mut syllables: Vec<Syllable> = Vec::new();
+        let mut long_start = false;
+
+        loop {
+            self.path.syllable = Some(syllables.len());
+            let (syllable, long) = self.syllable()?;
+
+            if long {
+                match syllables.last_mut() {
+                    Some(previous) => previous.coda = glide(syllable.onset),
+                    None => long_start = true,
+                }
+            }
+
+            syllables.push(syllable);
+
+            if !self.at_syllable_break() {
+                break;
+            }
+            self.pos += 1;
+        }
+
+        self.path.syllable = None;
+
+        Ok((Word(syllables), long_start))
+    }
+
+    // the opening marks before a word
+    fn open_spans(&mut self) {
+        let Some(enclosures) = &self.enclosures else {
+            return;
+        };
+        let start = enclosures.words;
+
+        while let Some(enclosure) = self.take(OPENING) {
+            let enclosures = self.enclosures.as_mut().unwrap();
+
+            enclosures.open.push(enclosures.spans.len());
+            enclosures.spans.push(Span {
+                enclosure,
+                start,
+                end: start,
+            });
+        }
+    }
+
+    // the closing marks after a word, which have to close the innermost open span
+    fn close_spans(&mut self) -> Result<(), ParseError> {
+        let Some(enclosures) = &mut self.enclosures else {
+            return Ok(());
+        };
+        enclosures.words += 1;
+
+        loop {
+            let Some(&innermost) = self.enclosures.as_ref().unwrap().open.last() else {
+                return Ok(());
+            };
+
+            let start = self.pos;
+            let Some(enclosure) = self.take(CLOSING) else {
+                return Ok(());
+            };
+
+            let enclosures = self.enclosures.as_mut().unwrap();
+            let span = &mut enclosures.spans[innermost];
+
+            if span.enclosure != enclosure {
+                let expected = Expected::Close(span.enclosure);
+                return Err(self.error_at(start, expected));
+            }
+
+            span.end = enclosures.words;
+            enclosures.open.pop();
+        }
+    }
+
+    fn phrase(&mut self) -> Result<(Phrase, bool), ParseError> {
+        let mut words: Vec<Word> = Vec::new();
+        let mut long_start = false;
+
+        loop {
+            self.path.word = Some(words.len());
+            self.open_spans();
+            let (word, long) = self.word()?;
+            self.close_spans()?;
+
+            if long {
+                let previous = words.last_mut().and_then(|word| word.0.last_mut());
+
+                match previous {
+                    Some(previous) => previous.coda = glide(word[0].onset),
+                    None => long_start = true,
+                }
+            }
+
+            words.push(word);
+
+            let before = self.pos;
+            self.skip_spaces();
+
+            if self.pos == before || !self.peek().is_some_and(|ch| self.starts_syllable(ch)) {
+                self.pos = before;
+                break;
+            }
+        }
+
+        self.path.word = None;
+
+        Ok((Phrase(words), long_start))
+    }
+
+    fn starts_syllable(&self, ch: char) -> bool {
+        !ch.is_whitespace() && !is_delimiter(ch) && !is_punctuation(ch)
+    }
+
+    fn sentence(&mut self) -> Result<Sentence, ParseError> {
+        let mut phrases = Vec::new();
+        let mut separators = Vec::new();
+
+        self.enclosures = Some(Enclosures::default());
+
+        loop {
+            self.path.phrase = Some(phrases.len());
+            let (phrase, _) = self.phrase()?;
+            phrases.push(phrase);
+
+            let before = self.pos;
+            self.skip_spaces();
+
+            match self.take(SEPARATORS) {
+                Some(separator) => separators.push(separator),
+                None => {
+                    self.pos = before;
+                    break;
+                }
+            }
+
+            self.skip_spaces();
+        }
+
+        self.path.phrase = None;
+
+        let enclosures = self.enclosures.take().unwrap_or_default();
+        if let Some(&innermost) = enclosures.open.last() {
+            let expected = Expected::Close(enclosures.spans[innermost].enclosure);
+            return Err(self.error(expected));
+        }
+
+        if separators
+            .iter()
+            .all(|separator| *separator == Separator::Phrase)
+        {
+            separators.clear();
+        }
+
+        Ok(Sentence {
+            phrases,
+            separators,
+            terminator: self.take(TERMINATORS).unwrap_or_default(),
+            spans: enclosures.spans,
+        })
+    }
+
+    fn paragraph(&mut self) -> Result<Paragraph, ParseError> {
+        let mut sentences = Vec::new();
+
+        loop {
+            self.path.sentence = Some(sentences.len());
+            let sentence = self.sentence()?;
+            sentences.push(sentence);
+
+            let terminated = self.eat(&['.']);
+            self.skip_spaces();
+
+            match self.peek() {
+                Some(ch) if terminated && self.starts_syllable(ch) => continue,
+                None | Some('\n') => break,
+                Some(ch) if is_delimiter(ch) => break,
+                Some(_) if terminated => return Err(self.error(Expected::EndOfInput)),
+                Some(_) => return Err(self.error(Expected::End)),
+            }
+        }
+
+        self.path.sentence = None;
+
+        Ok(Paragraph(sentences))
+    }
+
+    fn text(&mut self) -> Result<Text, ParseError> {
+        let mut paragraphs = Vec::new();
+
+        loop {
+            self.skip_blank();
+
+            match self.peek() {
+                Some(ch) if self.starts_syllable(ch) => {}
+                _ => break,
+            }
+
+            self.path.paragraph = Some(paragraphs.len());
+            let paragraph = self.paragraph()?;
+            paragraphs.push(paragraph);
+        }
+
+        self.path.paragraph = None;
+
+        Ok(Text(paragraphs))
+    }
+}
+
+fn glide(onset: C) -> Option<H> {
+    match onset {
+        C::H(h @ (H::W | H::Y)) => Some(h),
+        _ => None,
+    }
+}
+
+fn is_punctuation(ch: char) -> bool {
+    matches!(ch, '.' | ',' | '|' | '↗' | '↘' | '?' | '!')
+}
+
+fn is_delimiter(ch: char) -> bool {
+    matches!(ch, '/' | '[' | ']')
+}
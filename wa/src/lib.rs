@@ -1,13 +1,39 @@
-use std::{
-    f32::consts::E,
-    fmt::{Display, Write},
-    str::FromStr,
-};
+use std::{fmt::Display, str::FromStr};
 
 use derive_more::{Deref, DerefMut};
+use parser::Parser;
 use rand::Rng;
+use romanization::Romanize;
+use sandhi::{Sandhi, Transcribe};
 use serde_derive::*;
-use unicode_normalization::char::{compose, decompose_canonical};
+use unicode_normalization::char::{decompose_canonical, is_combining_mark};
+
+pub mod collation;
+pub mod distance;
+pub mod features;
+mod generator;
+mod ipa;
+mod ipa_parser;
+pub mod markov;
+pub mod numerals;
+mod parser;
+mod phonetic;
+pub mod phonotactics;
+pub mod romanization;
+pub mod sandhi;
+pub mod serde;
+pub mod sound_change;
+pub mod stats;
+mod syllabifier;
+#[cfg(feature = "synth")]
+pub mod synth;
+
+pub use generator::{ConfigError, Generator, GeneratorConfig, Length};
+pub use ipa::{Detail, IpaOptions, ToneStyle};
+pub use ipa_parser::{from_ipa, FromIpa};
+pub use parser::{Expected, Parse, ParseError, ParseMode, Path, SourceMap};
+pub use phonetic::{Alphabet, Phonetic};
+pub use syllabifier::syllabify;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum CStem {
@@ -18,17 +44,26 @@ pub enum CStem {
     R,
 }
 
+impl CStem {
+    pub const COUNT: usize = 5;
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        use CStem::*;
+        [P, T, K, S, R].get(index).copied()
+    }
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).filter_map(Self::from_index)
+    }
+}
+
 impl Random for CStem {
     fn random(rng: &mut impl Rng) -> Self {
-        use CStem::*;
-        match rng.gen_range(0..5) {
-            0 => P,
-            1 => T,
-            2 => K,
-            3 => S,
-            4 => R,
-            _ => unreachable!(),
-        }
+        GeneratorConfig::standard().stem(rng)
     }
 }
 
@@ -39,11 +74,38 @@ pub enum PureC {
     Sharp(CStem),
 }
 impl PureC {
+    pub const COUNT: usize = 3 * CStem::COUNT;
+
     pub fn stem(&self) -> CStem {
         match self {
             PureC::Strong(stem) | PureC::Blunt(stem) | PureC::Sharp(stem) => *stem,
         }
     }
+
+    pub fn index(&self) -> usize {
+        let kind = match self {
+            PureC::Strong(_) => 0,
+            PureC::Blunt(_) => 1,
+            PureC::Sharp(_) => 2,
+        };
+
+        kind * CStem::COUNT + self.stem().index()
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        let stem = CStem::from_index(index % CStem::COUNT)?;
+
+        match index / CStem::COUNT {
+            0 => Some(PureC::Strong(stem)),
+            1 => Some(PureC::Blunt(stem)),
+            2 => Some(PureC::Sharp(stem)),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).filter_map(Self::from_index)
+    }
 }
 
 impl Display for PureC {
@@ -78,15 +140,7 @@ impl Display for PureC {
 
 impl Random for PureC {
     fn random(rng: &mut impl Rng) -> Self {
-        let inner = CStem::random(rng);
-
-        use PureC::*;
-        match rng.gen_range(0..3) {
-            0 => Strong(inner),
-            1 => Blunt(inner),
-            2 => Sharp(inner),
-            _ => unreachable!(),
-        }
+        GeneratorConfig::standard().consonant(rng)
     }
 }
 
@@ -138,15 +192,25 @@ impl Display for H {
     }
 }
 
+impl H {
+    pub const COUNT: usize = 4;
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        [H::W, H::Y, H::X, H::H].get(index).copied()
+    }
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).filter_map(Self::from_index)
+    }
+}
+
 impl Random for H {
     fn random(rng: &mut impl Rng) -> Self {
-        match rng.gen_range(0..4) {
-            0 => H::W,
-            1 => H::Y,
-            2 => H::X,
-            3 => H::H,
-            _ => unreachable!(),
-        }
+        GeneratorConfig::standard().h(rng)
     }
 }
 
@@ -188,13 +252,31 @@ impl From<PureC> for C {
     }
 }
 
+impl C {
+    pub const COUNT: usize = H::COUNT + PureC::COUNT;
+
+    pub fn index(&self) -> usize {
+        match self {
+            C::H(h) => h.index(),
+            C::C(c) => H::COUNT + c.index(),
+        }
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        match index.checked_sub(H::COUNT) {
+            None => H::from_index(index).map(C::H),
+            Some(index) => PureC::from_index(index).map(C::C),
+        }
+    }
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).filter_map(Self::from_index)
+    }
+}
+
 impl Random for C {
     fn random(rng: &mut impl Rng) -> Self {
-        if rng.gen_bool(4. / 15.) {
-            C::H(H::random(rng))
-        } else {
-            C::C(PureC::random(rng))
-        }
+        GeneratorConfig::standard().onset(rng)
     }
 }
 
@@ -230,16 +312,25 @@ impl Display for V {
     }
 }
 
+impl V {
+    pub const COUNT: usize = 5;
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        [V::A, V::E, V::I, V::O, V::U].get(index).copied()
+    }
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).filter_map(Self::from_index)
+    }
+}
+
 impl Random for V {
     fn random(rng: &mut impl Rng) -> Self {
-        match rng.gen_range(0..5) {
-            0 => V::A,
-            1 => V::E,
-            2 => V::I,
-            3 => V::O,
-            4 => V::U,
-            _ => unreachable!(),
-        }
+        GeneratorConfig::standard().vowel(rng)
     }
 }
 
@@ -286,27 +377,129 @@ impl Display for T {
     }
 }
 
+impl T {
+    pub const COUNT: usize = 4;
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        [T::High, T::Low, T::Peaking, T::Nasal].get(index).copied()
+    }
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).filter_map(Self::from_index)
+    }
+
+    pub fn from_mark(ch: char) -> Option<T> {
+        match ch {
+            ',' | '\u{0301}' | '´' => Some(T::High),
+            '`' | '\u{0300}' => Some(T::Low),
+            '^' | '\u{0302}' => Some(T::Peaking),
+            '~' | '\u{0303}' => Some(T::Nasal),
+
+            // the numbered romanisation
+            '1' => Some(T::High),
+            '2' => Some(T::Low),
+            '3' => Some(T::Peaking),
+            '4' => Some(T::Nasal),
+
+            _ => None,
+        }
+    }
+
+    // the pitch over the course of the syllable, the first point is at time 0 and the last at 1
+    pub fn contour(&self) -> &'static [ContourPoint] {
+        const fn point(time: f32, pitch: f32) -> ContourPoint {
+            ContourPoint { time, pitch }
+        }
+
+        const HIGH: &[ContourPoint] = &[point(0., 1.), point(1., 1.)];
+        const LOW: &[ContourPoint] = &[point(0., 0.), point(1., 0.)];
+        const PEAKING: &[ContourPoint] = &[point(0., 0.5), point(0.5, 1.), point(1., 0.5)];
+
+        match self {
+            T::High => HIGH,
+            T::Low | T::Nasal => LOW,
+            T::Peaking => PEAKING,
+        }
+    }
+
+    // a nasal tone nasalises the vowel on top of its pitch
+    pub fn is_nasal(&self) -> bool {
+        *self == T::Nasal
+    }
+
+    // the contour's pitch at any time from 0 to 1, linearly interpolated
+    pub fn pitch_at(&self, time: f32) -> f32 {
+        let contour = self.contour();
+        let time = time.clamp(0., 1.);
+
+        for pair in contour.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+
+            if time <= to.time {
+                let t = (time - from.time) / (to.time - from.time);
+                return from.pitch + (to.pitch - from.pitch) * t;
+            }
+        }
+
+        contour[contour.len() - 1].pitch
+    }
+
+    // the contour as Chao levels from 1 (lowest) to 5 (highest), a level held between points is
+    // only given once
+    pub fn chao_levels(&self) -> Vec<u8> {
+        let mut levels: Vec<u8> = self
+            .contour()
+            .iter()
+            .map(|point| 1 + (point.pitch.clamp(0., 1.) * 4.).round() as u8)
+            .collect();
+
+        levels.dedup();
+        levels
+    }
+
+    pub fn chao_letters(&self) -> String {
+        self.chao_levels()
+            .into_iter()
+            .map(|level| ['˩', '˨', '˧', '˦', '˥'][level as usize - 1])
+            .collect()
+    }
+}
+
+// `pitch` is relative to the speaker's range, 0 at the bottom and 1 at the top
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContourPoint {
+    pub time: f32,
+    pub pitch: f32,
+}
+
 impl Random for T {
     fn random(rng: &mut impl Rng) -> Self {
-        match rng.gen_range(0..4) {
-            0 => T::High,
-            1 => T::Low,
-            2 => T::Peaking,
-            3 => T::Nasal,
-            _ => unreachable!(),
-        }
+        GeneratorConfig::standard().tone(rng)
     }
 }
 
+// a nasal tone on its own is the nasalisation it gives the vowel, its pitch is only written with
+// `IpaOptions::nasal_pitch`. a syllable always has both
 impl Ipa for T {
     fn ipa(&self) -> String {
         match self {
-            T::High => "˥",
-            T::Low => "˩",
-            T::Peaking => "˧˥˧",
-            T::Nasal => "\u{0303}",
+            T::Nasal => "\u{0303}".to_owned(),
+            _ => self.chao_letters(),
         }
-        .to_owned()
+    }
+
+    fn ipa_with(&self, options: &IpaOptions) -> String {
+        let mut ipa = self.ipa();
+
+        if self.is_nasal() && options.nasal_pitch {
+            ipa.push_str(&self.chao_letters());
+        }
+
+        options.format(&ipa)
     }
 }
 
@@ -315,16 +508,102 @@ pub enum Punctuation {
     WordBreak,
     PhraseBreak,
     SentenceBreak,
+    ListBreak,
+    QuestionBreak,
+    ExclamationBreak,
+    OpenQuotation,
+    CloseQuotation,
+    OpenParenthetical,
+    CloseParenthetical,
+}
+
+// how a sentence ends, `.`, `?` or `!`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Terminator {
+    #[default]
+    Statement,
+    Question,
+    Exclamation,
+}
+
+impl Terminator {
+    pub fn mark(&self) -> char {
+        match self {
+            Terminator::Statement => '.',
+            Terminator::Question => '?',
+            Terminator::Exclamation => '!',
+        }
+    }
+
+    pub fn punctuation(&self) -> Punctuation {
+        match self {
+            Terminator::Statement => Punctuation::SentenceBreak,
+            Terminator::Question => Punctuation::QuestionBreak,
+            Terminator::Exclamation => Punctuation::ExclamationBreak,
+        }
+    }
+}
+
+// what comes between two phrases of a sentence, `:` or `;` between the items of a list
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Separator {
+    #[default]
+    Phrase,
+    List,
+}
+
+impl Separator {
+    pub fn mark(&self) -> char {
+        match self {
+            Separator::Phrase => ':',
+            Separator::List => ';',
+        }
+    }
+
+    pub fn punctuation(&self) -> Punctuation {
+        match self {
+            Separator::Phrase => Punctuation::PhraseBreak,
+            Separator::List => Punctuation::ListBreak,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Enclosure {
+    // “quoted”, or "quoted" in ASCII
+    Quotation,
+    // (parenthetical)
+    Parenthetical,
+}
+
+impl Enclosure {
+    pub fn open(&self) -> Punctuation {
+        match self {
+            Enclosure::Quotation => Punctuation::OpenQuotation,
+            Enclosure::Parenthetical => Punctuation::OpenParenthetical,
+        }
+    }
+
+    pub fn close(&self) -> Punctuation {
+        match self {
+            Enclosure::Quotation => Punctuation::CloseQuotation,
+            Enclosure::Parenthetical => Punctuation::CloseParenthetical,
+        }
+    }
+}
+
+// the words `start..end` of a sentence, counted across its phrases, in quotes or parentheses.
+// spans nest, and a sentence keeps them ordered by where they open with the outer one first
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Span {
+    pub enclosure: Enclosure,
+    pub start: usize,
+    pub end: usize,
 }
 
 impl Random for Punctuation {
     fn random(rng: &mut impl Rng) -> Self {
-        match rng.gen_range(0..10) {
-            0..=3 => Punctuation::WordBreak,
-            4..=6 => Punctuation::PhraseBreak,
-            7..=9 => Punctuation::SentenceBreak,
-            _ => unreachable!(),
-        }
+        GeneratorConfig::standard().punctuation(rng)
     }
 }
 
@@ -336,48 +615,96 @@ pub struct Syllable {
     pub coda: Option<H>,
 }
 
-impl Display for Syllable {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.onset.fmt(f)?;
+impl Syllable {
+    // every syllable has an index below this, they are numbered in the same order `Ord` sorts them
+    pub const COUNT: usize = C::COUNT * V::COUNT * T::COUNT * CODA_COUNT;
 
-        let v = self.vowel.to_string().chars().next().unwrap();
-        let t = self.tone.to_string().chars().next().unwrap();
+    pub fn index(&self) -> u16 {
+        let coda = self.coda.map_or(0, |coda| coda.index() + 1);
 
-        if let Some(ch) = compose(v, t) {
-            f.write_char(ch)?
-        } else {
-            f.write_char(v)?;
-            f.write_char(t)?;
-        }
+        (((self.onset.index() * V::COUNT + self.vowel.index()) * T::COUNT + self.tone.index())
+            * CODA_COUNT
+            + coda) as u16
+    }
+
+    pub fn from_index(index: u16) -> Option<Self> {
+        let index = index as usize;
 
-        if let Some(coda) = &self.coda {
-            coda.fmt(f)?;
+        if index >= Self::COUNT {
+            return None;
         }
 
-        Ok(())
+        let coda = index % CODA_COUNT;
+        let index = index / CODA_COUNT;
+        let tone = index % T::COUNT;
+        let index = index / T::COUNT;
+        let vowel = index % V::COUNT;
+        let onset = index / V::COUNT;
+
+        Some(Syllable {
+            onset: C::from_index(onset)?,
+            vowel: V::from_index(vowel)?,
+            tone: T::from_index(tone)?,
+            coda: match coda {
+                0 => None,
+                coda => Some(H::from_index(coda - 1)?),
+            },
+        })
+    }
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT as u16).filter_map(Self::from_index)
+    }
+}
+
+// no coda, or one of the Hs
+const CODA_COUNT: usize = H::COUNT + 1;
+
+impl Display for Syllable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.romanize_into(romanization::for_formatter(f), f)
     }
 }
 
 impl FromStr for Syllable {
-    type Err = usize;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let chars = s.chars().collect::<Vec<_>>();
+        Self::parse_with(s, ParseMode::Strict)
+    }
+}
+
+impl Parse for Syllable {
+    fn parse_with(s: &str, mode: ParseMode) -> Result<Self, ParseError> {
+        let chars = s.char_indices().collect::<Vec<_>>();
         let mut index = 0;
 
+        // the byte range covering the chars `from..to`
+        let span = |from: usize, to: usize| {
+            let start = chars.get(from).map_or(s.len(), |(i, _)| *i);
+            let end = chars.get(to).map_or(s.len(), |(i, _)| *i);
+            start..end
+        };
+        let unexpected = |from: usize, to: usize, expected: Expected| {
+            ParseError::new(span(from, to), expected, chars.get(from).map(|(_, ch)| *ch))
+        };
+
         // parse initial consonant
 
-        let first = *chars.get(index).ok_or(index)?;
-        let second = *chars.get(index + 1).ok_or(index + 1)?;
+        let first = chars
+            .get(index)
+            .ok_or_else(|| unexpected(index, index + 1, Expected::Onset))?
+            .1;
+        let second = chars.get(index + 1).map(|(_, ch)| *ch);
 
-        let compound = if second == 'y' || second == 'w' {
+        let compound = if let Some(second @ ('y' | 'w')) = second {
             index += 1;
-            true
+            Some(second)
         } else {
-            false
+            None
         };
 
-        let onset = if compound {
+        let onset = if let Some(second) = compound {
             let str = format!("{first}{second}");
             match str.as_str() {
                 "py" => PureC::Sharp(CStem::P),
@@ -386,7 +713,7 @@ impl FromStr for Syllable {
                 "sy" => PureC::Sharp(CStem::S),
                 "rw" => PureC::Blunt(CStem::R),
 
-                _ => return Err(index - 1),
+                _ => return Err(unexpected(index - 1, index + 1, Expected::Onset)),
             }
             .into()
         } else {
@@ -407,17 +734,21 @@ impl FromStr for Syllable {
                 'x' => H::X.into(),
                 'h' => H::H.into(),
 
-                _ => return Err(index),
+                _ => return Err(unexpected(index, index + 1, Expected::Onset)),
             }
         };
 
         index += 1;
 
         // parse vowel
-        let ch = *chars.get(index).ok_or(index)?;
+        let ch = chars
+            .get(index)
+            .ok_or_else(|| unexpected(index, index + 1, Expected::Vowel))?
+            .1;
 
         let mut vowel_ch = ch;
         let mut tone_ch = None;
+        let mut extra_ch = None;
         let mut counter = 0;
 
         decompose_canonical(ch, |ch| {
@@ -425,6 +756,8 @@ impl FromStr for Syllable {
                 vowel_ch = ch;
             } else if counter == 1 {
                 tone_ch = Some(ch);
+            } else if counter == 2 {
+                extra_ch = Some(ch);
             } else {
                 return;
             }
@@ -439,37 +772,46 @@ impl FromStr for Syllable {
             'o' => V::O,
             'u' => V::U,
 
-            _ => {
-                eprintln!("invalid char: '{ch}'");
-                return Err(index);
-            }
+            _ => return Err(unexpected(index, index + 1, Expected::Vowel)),
         };
 
         index += 1;
 
         // parse tone
+        let tone_err = |ch: char| {
+            // a tone composed into the vowel is reported at the vowel
+            let at = if tone_ch.is_some() { index - 1 } else { index };
+            ParseError::new(span(at, at + 1), Expected::Tone, Some(ch))
+        };
+
         let ch = if let Some(tone_ch) = tone_ch {
             tone_ch
         } else {
-            chars.get(index).copied().ok_or(index)?
+            chars
+                .get(index)
+                .ok_or_else(|| unexpected(index, index + 1, Expected::Tone))?
+                .1
         };
 
-        let tone = match ch {
-            ',' | '\u{0301}' | '´' => T::High,
-            '`' | '\u{0300}' => T::Low,
-            '^' | '\u{0302}' => T::Peaking,
-            '~' | '\u{0303}' => T::Nasal,
+        let tone = T::from_mark(ch).ok_or_else(|| tone_err(ch))?;
 
-            _ => {
-                eprintln!("invalid char: '{ch}'");
-                return Err(index);
-            }
-        };
+        if tone_ch.is_none() {
+            index += 1;
+        }
 
-        index += 1;
+        // a vowel can carry more marks than just its tone
+        if mode == ParseMode::Strict {
+            if let Some(extra) = extra_ch {
+                return Err(ParseError::new(
+                    span(index - 1, index),
+                    Expected::End,
+                    Some(extra),
+                ));
+            }
+        }
 
         // parse coda
-        let coda = chars.get(index).and_then(|ch| match ch {
+        let coda = chars.get(index).and_then(|(_, ch)| match ch {
             'w' => Some(H::W),
             'y' => Some(H::Y),
             'x' => Some(H::X),
@@ -478,6 +820,23 @@ impl FromStr for Syllable {
             _ => None,
         });
 
+        if coda.is_some() {
+            index += 1;
+        }
+
+        if mode == ParseMode::Strict && index < chars.len() {
+            let expected = if coda.is_some() {
+                Expected::End
+            } else {
+                match chars[index].1 {
+                    ch if T::from_mark(ch).is_some() || is_combining_mark(ch) => Expected::End,
+                    _ => Expected::Coda,
+                }
+            };
+
+            return Err(unexpected(index, index + 1, expected));
+        }
+
         Ok(Syllable {
             onset,
             vowel,
@@ -485,50 +844,31 @@ impl FromStr for Syllable {
             coda,
         })
     }
+
+    fn parse_spanned(s: &str, mode: ParseMode) -> Result<(Self, SourceMap), ParseError> {
+        let syllable = Self::parse_with(s, mode)?;
+
+        let mut spans = SourceMap::default();
+        spans.insert(Path::default(), 0..s.len());
+
+        Ok((syllable, spans))
+    }
 }
 
 impl Random for Syllable {
     fn random(rng: &mut impl Rng) -> Self {
-        Self {
-            onset: C::random(rng),
-            vowel: V::random(rng),
-            tone: T::random(rng),
-            coda: if rng.gen_bool(4. / 5.) {
-                Some(H::random(rng))
-            } else {
-                None
-            },
-        }
+        GeneratorConfig::standard().syllable(rng)
     }
 }
 
 impl Ipa for Syllable {
     fn ipa(&self) -> String {
-        let mut buffer = String::new();
-
-        buffer.push_str(&self.onset.ipa());
-        buffer.push_str(&self.vowel.ipa());
-
-        if self.tone == T::Nasal {
-            buffer.push_str(&self.tone.ipa());
-        }
-
-        if let Some(coda) = &self.coda {
-            buffer.push_str(&coda.ipa());
-        };
-
-        if self.tone != T::Nasal {
-            buffer.push_str(&self.tone.ipa());
-        } else {
-            buffer.push_str(&T::Low.ipa());
-        }
-
-        buffer
+        sandhi::Phones::from(self).to_string()
     }
 }
 
 pub fn syllable(str: &str) -> Syllable {
-    str.parse().unwrap()
+    parse_or_panic(str)
 }
 
 #[derive(Clone, Deref, DerefMut, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -546,80 +886,46 @@ impl IntoIterator for Word {
 
 impl Display for Word {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut first = true;
-        for syllable in &self.0 {
-            if first {
-                first = false;
-            } else {
-                f.write_char('-')?;
-            }
-
-            syllable.fmt(f)?;
-        }
-
-        Ok(())
+        self.romanize_into(romanization::for_formatter(f), f)
     }
 }
 
 impl FromStr for Word {
-    type Err = (usize, usize);
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let syllables = s.split('-');
-
-        let mut word = Vec::new();
+        Self::parse_with(s, ParseMode::Strict)
+    }
+}
 
-        for (i, syllable) in syllables.enumerate() {
-            word.push(syllable.parse().map_err(|e| (i, e))?);
-        }
+impl Parse for Word {
+    fn parse_with(s: &str, mode: ParseMode) -> Result<Self, ParseError> {
+        Parser::new(s, mode).parse(Parser::word)
+    }
 
-        Ok(Word(word))
+    fn parse_spanned(s: &str, mode: ParseMode) -> Result<(Self, SourceMap), ParseError> {
+        Parser::new(s, mode).parse_spanned(Parser::word)
     }
 }
 
 impl Random for Word {
     fn random(rng: &mut impl Rng) -> Self {
-        let mut b = Vec::new();
-
-        let mut n = 1.;
-
-        loop {
-            let chance = 1. / n;
-
-            if rng.gen_bool(chance) {
-                b.push(Syllable::random(rng))
-            } else {
-                break;
-            }
-
-            n += 1.;
-        }
-
-        Self(b)
+        GeneratorConfig::standard().word(rng)
     }
 }
 
 impl Ipa for Word {
     fn ipa(&self) -> String {
-        let mut buffer = String::new();
-
-        let mut first = true;
-        for syllable in &self.0 {
-            if first {
-                first = false;
-            } else {
-                buffer.push('.')
-            }
-
-            buffer.push_str(&syllable.ipa())
-        }
+        self.transcribe(Sandhi::standard())
+    }
 
-        buffer
+    fn ipa_with(&self, options: &IpaOptions) -> String {
+        options.format(&self.transcribe(options.sandhi()))
     }
 }
 
 pub fn word(word: &str) -> Word {
-    word.parse().unwrap()
+    parse_or_panic(word)
 }
 
 #[derive(Clone, Deref, DerefMut, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -637,83 +943,90 @@ impl IntoIterator for Phrase {
 
 impl Display for Phrase {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut first = true;
-        for word in &self.0 {
-            if first {
-                first = false;
-            } else {
-                f.write_char(' ')?;
-            }
-            word.fmt(f)?;
-        }
-
-        Ok(())
+        self.romanize_into(romanization::for_formatter(f), f)
     }
 }
 
 impl FromStr for Phrase {
-    type Err = (usize, usize, usize);
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let words = s.split_whitespace();
-
-        let mut phrase = Vec::new();
+        Self::parse_with(s, ParseMode::Strict)
+    }
+}
 
-        for (i, word) in words.enumerate() {
-            phrase.push(word.parse().map_err(|(e1, e2)| (i, e1, e2))?);
-        }
+impl Parse for Phrase {
+    fn parse_with(s: &str, mode: ParseMode) -> Result<Self, ParseError> {
+        Parser::new(s, mode).parse(Parser::phrase)
+    }
 
-        Ok(Phrase(phrase))
+    fn parse_spanned(s: &str, mode: ParseMode) -> Result<(Self, SourceMap), ParseError> {
+        Parser::new(s, mode).parse_spanned(Parser::phrase)
     }
 }
 
 impl Random for Phrase {
     fn random(rng: &mut impl Rng) -> Self {
-        let mut b = Vec::new();
-
-        let mut n = 1.;
-
-        loop {
-            let chance = 1. / n;
-
-            if rng.gen_bool(chance) {
-                b.push(Word::random(rng))
-            } else {
-                break;
-            }
-
-            n += 1.;
-        }
-
-        Self(b)
+        GeneratorConfig::standard().phrase(rng)
     }
 }
 
 impl Ipa for Phrase {
     fn ipa(&self) -> String {
-        let mut buffer = String::new();
+        self.transcribe(Sandhi::standard())
+    }
 
-        let mut first = true;
-        for word in &self.0 {
-            if first {
-                first = false;
-            } else {
-                buffer.push(' ')
-            }
+    fn ipa_with(&self, options: &IpaOptions) -> String {
+        options.format(&self.transcribe(options.sandhi()))
+    }
+}
 
-            buffer.push_str(&word.ipa())
+pub fn phrase(phrase: &str) -> Phrase {
+    parse_or_panic(phrase)
+}
+
+#[derive(Clone, Deref, DerefMut, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Sentence {
+    #[deref]
+    #[deref_mut]
+    pub phrases: Vec<Phrase>,
+    // the separator before each phrase after the first, missing ones are phrase breaks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub separators: Vec<Separator>,
+    #[serde(default)]
+    pub terminator: Terminator,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<Span>,
+}
+
+impl Sentence {
+    // a statement with its phrases separated by phrase breaks
+    pub fn new(phrases: Vec<Phrase>) -> Self {
+        Self {
+            phrases,
+            ..Self::default()
         }
+    }
 
-        buffer
+    // the separator before the `i`th phrase
+    pub fn separator(&self, i: usize) -> Separator {
+        i.checked_sub(1)
+            .and_then(|i| self.separators.get(i))
+            .copied()
+            .unwrap_or_default()
     }
-}
 
-pub fn phrase(phrase: &str) -> Phrase {
-    phrase.parse().unwrap()
+    // the words of every phrase in order, the indices spans count
+    pub fn words(&self) -> impl Iterator<Item = &Word> {
+        self.phrases.iter().flat_map(|phrase| phrase.iter())
+    }
 }
 
-#[derive(Clone, Deref, DerefMut, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
-pub struct Sentence(#[deref] pub Vec<Phrase>);
+impl From<Vec<Phrase>> for Sentence {
+    fn from(phrases: Vec<Phrase>) -> Self {
+        Self::new(phrases)
+    }
+}
 
 impl IntoIterator for Sentence {
     type Item = Phrase;
@@ -721,87 +1034,52 @@ impl IntoIterator for Sentence {
     type IntoIter = <Vec<Phrase> as IntoIterator>::IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.phrases.into_iter()
     }
 }
 
 impl Display for Sentence {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut first = true;
-        for phrase in &self.0 {
-            if first {
-                first = false;
-            } else {
-                f.write_str(" ")?;
-            }
-            phrase.fmt(f)?;
-        }
-
-        f.write_char('.')?;
-
-        Ok(())
+        self.romanize_into(romanization::for_formatter(f), f)
     }
 }
 
 impl FromStr for Sentence {
-    type Err = (usize, usize, usize, usize);
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let phrases = s.split(": ");
-
-        let mut sentence = Vec::new();
+        Self::parse_with(s, ParseMode::Strict)
+    }
+}
 
-        for (i, phrase) in phrases.enumerate() {
-            sentence.push(phrase.parse().map_err(|(e1, e2, e3)| (i, e1, e2, e3))?);
-        }
+impl Parse for Sentence {
+    fn parse_with(s: &str, mode: ParseMode) -> Result<Self, ParseError> {
+        Parser::new(s, mode).parse(Parser::sentence)
+    }
 
-        Ok(Sentence(sentence))
+    fn parse_spanned(s: &str, mode: ParseMode) -> Result<(Self, SourceMap), ParseError> {
+        Parser::new(s, mode).parse_spanned(Parser::sentence)
     }
 }
 
 impl Random for Sentence {
     fn random(rng: &mut impl Rng) -> Self {
-        let mut b = Vec::new();
-
-        let mut n = 1.;
-
-        loop {
-            let chance = 1. / n;
-
-            if rng.gen_bool(chance) {
-                b.push(Phrase::random(rng))
-            } else {
-                break;
-            }
-
-            n += 1.;
-        }
-
-        Self(b)
+        GeneratorConfig::standard().sentence(rng)
     }
 }
 
 impl Ipa for Sentence {
     fn ipa(&self) -> String {
-        let mut buffer = String::new();
-
-        let mut first = true;
-        for phrase in &self.0 {
-            if first {
-                first = false;
-            } else {
-                buffer.push_str(", ")
-            }
-
-            buffer.push_str(&phrase.ipa())
-        }
+        self.transcribe(Sandhi::standard())
+    }
 
-        buffer
+    fn ipa_with(&self, options: &IpaOptions) -> String {
+        options.format(&self.transcribe(options.sandhi()))
     }
 }
 
 pub fn sentence(sentence: &str) -> Sentence {
-    sentence.parse().unwrap()
+    parse_or_panic(sentence)
 }
 
 #[derive(Clone, Deref, DerefMut, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -819,72 +1097,46 @@ impl IntoIterator for Paragraph {
 
 impl Display for Paragraph {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for sentence in &self.0 {
-            sentence.fmt(f)?;
-            f.write_char(' ')?;
-        }
-
-        Ok(())
+        self.romanize_into(romanization::for_formatter(f), f)
     }
 }
 
 impl FromStr for Paragraph {
-    type Err = (usize, usize, usize, usize, usize);
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let sentences = s.split(". ");
-
-        let mut paragraph = Vec::new();
+        Self::parse_with(s, ParseMode::Strict)
+    }
+}
 
-        for (i, sentence) in sentences.enumerate() {
-            paragraph.push(
-                sentence
-                    .parse()
-                    .map_err(|(e1, e2, e3, e4)| (i, e1, e2, e3, e4))?,
-            );
-        }
+impl Parse for Paragraph {
+    fn parse_with(s: &str, mode: ParseMode) -> Result<Self, ParseError> {
+        Parser::new(s, mode).parse(Parser::paragraph)
+    }
 
-        Ok(Paragraph(paragraph))
+    fn parse_spanned(s: &str, mode: ParseMode) -> Result<(Self, SourceMap), ParseError> {
+        Parser::new(s, mode).parse_spanned(Parser::paragraph)
     }
 }
 
 impl Random for Paragraph {
     fn random(rng: &mut impl Rng) -> Self {
-        let mut b = Vec::new();
-
-        let mut n = 1.;
-
-        loop {
-            let chance = 1. / n;
-
-            if rng.gen_bool(chance) {
-                b.push(Sentence::random(rng))
-            } else {
-                break;
-            }
-
-            n += 1.;
-        }
-
-        Self(b)
+        GeneratorConfig::standard().paragraph(rng)
     }
 }
 
 impl Ipa for Paragraph {
     fn ipa(&self) -> String {
-        let mut buffer = String::new();
-
-        for sentence in &self.0 {
-            buffer.push_str(&sentence.ipa());
-            buffer.push_str(". ");
-        }
+        self.transcribe(Sandhi::standard())
+    }
 
-        buffer
+    fn ipa_with(&self, options: &IpaOptions) -> String {
+        options.format(&self.transcribe(options.sandhi()))
     }
 }
 
 pub fn paragraph(paragraph: &str) -> Paragraph {
-    paragraph.parse().unwrap()
+    parse_or_panic(paragraph)
 }
 
 #[derive(Clone, Deref, DerefMut, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -902,72 +1154,75 @@ impl IntoIterator for Text {
 
 impl Display for Text {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for sentence in &self.0 {
-            sentence.fmt(f)?;
-            f.write_char('\n')?;
-        }
-
-        Ok(())
+        self.romanize_into(romanization::for_formatter(f), f)
     }
 }
 
 impl FromStr for Text {
-    type Err = (usize, usize, usize, usize, usize, usize);
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let paragraphs = s.split('\n');
-
-        let mut text = Vec::new();
+        Self::parse_with(s, ParseMode::Strict)
+    }
+}
 
-        for (i, paragraph) in paragraphs.enumerate() {
-            text.push(
-                paragraph
-                    .parse()
-                    .map_err(|(e1, e2, e3, e4, e5)| (i, e1, e2, e3, e4, e5))?,
-            );
-        }
+impl Parse for Text {
+    fn parse_with(s: &str, mode: ParseMode) -> Result<Self, ParseError> {
+        Parser::new(s, mode).parse(Parser::text)
+    }
 
-        Ok(Text(text))
+    fn parse_spanned(s: &str, mode: ParseMode) -> Result<(Self, SourceMap), ParseError> {
+        Parser::new(s, mode).parse_spanned(Parser::text)
     }
 }
 
 impl Random for Text {
     fn random(rng: &mut impl Rng) -> Self {
-        let mut b = Vec::new();
-
-        let mut n = 1.;
-
-        loop {
-            let chance = 1. / n;
-
-            if rng.gen_bool(chance) {
-                b.push(Paragraph::random(rng))
-            } else {
-                break;
-            }
-
-            n += 1.;
-        }
-
-        Self(b)
+        GeneratorConfig::standard().text(rng)
     }
 }
 
 impl Ipa for Text {
     fn ipa(&self) -> String {
-        let mut buffer = String::new();
-
-        for paragraph in &self.0 {
-            buffer.push_str(&paragraph.ipa());
-            buffer.push('\n');
-        }
+        self.transcribe(Sandhi::standard())
+    }
 
-        buffer
+    fn ipa_with(&self, options: &IpaOptions) -> String {
+        options.format(&self.transcribe(options.sandhi()))
     }
 }
 
 pub fn text(text: &str) -> Text {
-    text.parse().unwrap()
+    parse_or_panic(text)
+}
+
+pub fn parse<L>(input: &str) -> Result<L, ParseError>
+where
+    L: Parse,
+{
+    L::parse_with(input, ParseMode::Strict)
+}
+
+pub fn parse_with<L>(input: &str, mode: ParseMode) -> Result<L, ParseError>
+where
+    L: Parse,
+{
+    L::parse_with(input, mode)
+}
+
+pub fn parse_spanned<L>(input: &str, mode: ParseMode) -> Result<(L, SourceMap), ParseError>
+where
+    L: Parse,
+{
+    L::parse_spanned(input, mode)
+}
+
+// the helpers above are meant for literals, a typo there is a bug so a readable panic is fine
+fn parse_or_panic<L>(input: &str) -> L
+where
+    L: Parse,
+{
+    parse(input).unwrap_or_else(|error| panic!("invalid wa notation\n{}", error.render(input)))
 }
 
 pub trait Random {
@@ -976,4 +1231,8 @@ pub trait Random {
 
 pub trait Ipa {
     fn ipa(&self) -> String;
+
+    fn ipa_with(&self, options: &IpaOptions) -> String {
+        options.format(&self.ipa())
+    }
 }
@@ -1,22 +1,163 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
 use clap::{Parser, Subcommand, ValueEnum};
-use wa::Ipa;
+use rand::{rngs::StdRng, SeedableRng};
+use wa::{
+    markov::Model,
+    phonotactics::{Check, Phonotactics},
+    romanization::{self, Romanization, Romanize},
+    sound_change::{Develop, History},
+    stats::Stats,
+    Ipa,
+};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    /// what the input is parsed as, and what random and generate make
     #[arg(short, long, value_enum, default_value_t=TextType::Text)]
     text_type: TextType,
 
+    /// ignore anything trailing a syllable instead of rejecting it, and split words written without
+    /// hyphens into syllables
+    #[arg(short, long)]
+    lenient: bool,
+
+    /// a JSON file of phonotactic rules that parsed and randomly generated text has to follow
+    #[arg(long, global = true)]
+    phonotactics: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    Pretty { raw: String },
-    Ipa { raw: String },
+    /// writes the text in a romanization scheme
+    Pretty {
+        /// the text in native notation
+        raw: String,
+        #[arg(short, long, value_enum, default_value_t = Scheme::Unicode)]
+        scheme: Scheme,
+    },
+    /// transcribes the text
+    Ipa {
+        /// the text in native notation
+        raw: String,
+        /// any of narrow, broad, numbers, ascii and delimited, separated by commas
+        #[arg(short, long, value_enum, value_delimiter = ',')]
+        style: Vec<Style>,
+        /// the IPA, or one of its ASCII alphabets
+        #[arg(short, long, value_enum, default_value_t = Format::Ipa)]
+        format: Format,
+        /// a file of sandhi rules to use instead of the standard ones
+        #[arg(long)]
+        sandhi: Option<PathBuf>,
+    },
+    /// applies a file of sound changes to every word of the text
+    Develop {
+        /// the text in native notation
+        raw: String,
+        /// the sound changes, one per line and applied in order
+        #[arg(short, long)]
+        changes: PathBuf,
+        /// print how each word got there
+        #[arg(long)]
+        trace: bool,
+    },
+    /// counts onsets, vowels, tones, codas, syllables, word lengths and bigrams across texts
+    Stats {
+        /// text files in native notation
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// print the counts as JSON
+        #[arg(long)]
+        json: bool,
+        /// only list the most frequent of each
+        #[arg(long)]
+        top: Option<usize>,
+    },
+    /// learns how the texts' words and sentences are put together and writes it to a JSON model
+    Train {
+        /// text files in native notation
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// where to write the model
+        #[arg(short, long)]
+        out: PathBuf,
+    },
+    /// imitates the texts a model was trained on
+    Generate {
+        /// a model written by train
+        #[arg(short, long)]
+        model: PathBuf,
+        /// the same seed makes the same text
+        #[arg(short, long)]
+        seed: Option<u64>,
+    },
+    /// makes up text
+    Random {
+        /// a JSON generator configuration, missing fields keep their defaults
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+        /// the same seed makes the same text, in place of the configuration's
+        #[arg(short, long)]
+        seed: Option<u64>,
+    },
+    /// writes the text as speech to a WAV file
+    #[cfg(feature = "synth")]
+    Speak {
+        /// the text in native notation
+        raw: String,
+        /// the WAV file to write
+        #[arg(short, long)]
+        out: PathBuf,
+        /// a JSON voice, missing fields keep their defaults
+        #[arg(short, long)]
+        voice: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+enum Scheme {
+    /// xúy
+    #[default]
+    Unicode,
+    /// xu,y
+    Ascii,
+    /// xu1y
+    Numbered,
+}
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+enum Format {
+    /// the International Phonetic Alphabet
+    #[default]
+    Ipa,
+    /// X-SAMPA
+    Xsampa,
+    /// Kirshenbaum
+    Kirshenbaum,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Style {
+    /// phonetic, with the sandhi rules applied
+    Narrow,
+    /// phonemic, without sandhi
+    Broad,
+    /// Chao tone numbers instead of tone letters
+    Numbers,
+    /// only ASCII characters, with tone numbers
+    Ascii,
+    /// in /slashes/ or [brackets]
+    Delimited,
 }
 
 #[derive(Clone, Copy, Debug, Default, ValueEnum)]
@@ -30,32 +171,356 @@ enum TextType {
     Text,
 }
 
-fn main() {
+fn main() -> ExitCode {
     macro_rules! match_text_type {
-        ($text_type:expr, $raw_text:expr, $ty:ty) => {{
-            let out: Box<$ty> = match $text_type {
-                TextType::Syllable => Box::new(wa::syllable($raw_text)),
-                TextType::Word => Box::new(wa::word($raw_text)),
-                TextType::Phrase => Box::new(wa::phrase($raw_text)),
-                TextType::Sentence => Box::new(wa::sentence($raw_text)),
-                TextType::Paragraph => Box::new(wa::paragraph($raw_text)),
-                TextType::Text => Box::new(wa::text($raw_text)),
+        ($text_type:expr, $raw_text:expr, $mode:expr, $rules:expr, $ty:ty) => {{
+            let out: Result<Box<$ty>, wa::ParseError> = match $text_type {
+                TextType::Syllable => {
+                    parse::<wa::Syllable>($raw_text, $mode, $rules).map(|x| Box::new(x) as _)
+                }
+                TextType::Word => {
+                    parse::<wa::Word>($raw_text, $mode, $rules).map(|x| Box::new(x) as _)
+                }
+                TextType::Phrase => {
+                    parse::<wa::Phrase>($raw_text, $mode, $rules).map(|x| Box::new(x) as _)
+                }
+                TextType::Sentence => {
+                    parse::<wa::Sentence>($raw_text, $mode, $rules).map(|x| Box::new(x) as _)
+                }
+                TextType::Paragraph => {
+                    parse::<wa::Paragraph>($raw_text, $mode, $rules).map(|x| Box::new(x) as _)
+                }
+                TextType::Text => {
+                    parse::<wa::Text>($raw_text, $mode, $rules).map(|x| Box::new(x) as _)
+                }
             };
 
-            out
+            match out {
+                Ok(out) => out,
+                Err(error) => {
+                    eprint!("{}", error.render($raw_text));
+                    return ExitCode::FAILURE;
+                }
+            }
         }};
     }
 
     let args = Args::parse();
 
     let text_type = args.text_type;
+    let mode = if args.lenient {
+        wa::ParseMode::Lenient
+    } else {
+        wa::ParseMode::Strict
+    };
+
+    let phonotactics: Option<Phonotactics> = match args.phonotactics.map(read_json).transpose() {
+        Ok(phonotactics) => phonotactics,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let rules = phonotactics.as_ref();
 
     match args.command {
-        Commands::Pretty { raw } => {
-            println!("{}", match_text_type!(text_type, &raw, dyn Display));
+        Commands::Pretty { raw, scheme } => {
+            let scheme: &dyn Romanization = match scheme {
+                Scheme::Unicode => &romanization::Unicode,
+                Scheme::Ascii => &romanization::Ascii,
+                Scheme::Numbered => &romanization::Numbered,
+            };
+
+            let out = match_text_type!(text_type, &raw, mode, rules, dyn Romanize);
+            println!("{}", out.romanize(scheme));
+        }
+        Commands::Ipa {
+            raw,
+            style,
+            format,
+            sandhi,
+        } => {
+            let mut options = wa::IpaOptions::narrow();
+
+            for style in style {
+                match style {
+                    Style::Narrow => options.detail = wa::Detail::Narrow,
+                    Style::Broad => options.detail = wa::Detail::Broad,
+                    Style::Numbers => options.tones = wa::ToneStyle::Numbers,
+                    Style::Ascii => options.ascii = true,
+                    Style::Delimited => options.delimit = true,
+                }
+            }
+
+            options.sandhi = match sandhi.map(read_sandhi).transpose() {
+                Ok(sandhi) => sandhi,
+                Err(error) => {
+                    eprintln!("{error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let alphabet = match format {
+                Format::Ipa => None,
+                Format::Xsampa => Some(wa::Alphabet::XSampa),
+                Format::Kirshenbaum => Some(wa::Alphabet::Kirshenbaum),
+            };
+
+            let out = match alphabet {
+                None => match_text_type!(text_type, &raw, mode, rules, dyn Ipa).ipa_with(&options),
+                Some(alphabet) => match_text_type!(text_type, &raw, mode, rules, dyn wa::Phonetic)
+                    .phonetic_with(alphabet, &options),
+            };
+
+            println!("{out}");
         }
-        Commands::Ipa { raw } => {
-            println!("{}", match_text_type!(text_type, &raw, dyn Ipa).ipa());
+        Commands::Develop {
+            raw,
+            changes,
+            trace,
+        } => {
+            let history = match read_history(changes) {
+                Ok(history) => history,
+                Err(error) => {
+                    eprintln!("{error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let developed = match text_type {
+                TextType::Syllable => develop::<wa::Syllable>(&raw, mode, rules, &history, trace),
+                TextType::Word => develop::<wa::Word>(&raw, mode, rules, &history, trace),
+                TextType::Phrase => develop::<wa::Phrase>(&raw, mode, rules, &history, trace),
+                TextType::Sentence => develop::<wa::Sentence>(&raw, mode, rules, &history, trace),
+                TextType::Paragraph => develop::<wa::Paragraph>(&raw, mode, rules, &history, trace),
+                TextType::Text => develop::<wa::Text>(&raw, mode, rules, &history, trace),
+            };
+
+            if !developed {
+                return ExitCode::FAILURE;
+            }
+        }
+        Commands::Stats { files, json, top } => {
+            let mut stats = Stats::new();
+
+            for path in files {
+                match read_text(&path, mode, rules) {
+                    Ok(text) => stats.add(&text),
+                    Err(error) => {
+                        eprintln!("{error}");
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+
+            if json {
+                let json = serde_json::to_string_pretty(&stats).expect("stats are valid JSON");
+                println!("{json}");
+            } else if let Some(top) = top {
+                print!("{stats:.top$}");
+            } else {
+                print!("{stats}");
+            }
+        }
+        Commands::Train { files, out } => {
+            let mut model = Model::new();
+
+            for path in files {
+                match read_text(&path, mode, rules) {
+                    Ok(text) => model.train(&text),
+                    Err(error) => {
+                        eprintln!("{error}");
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+
+            let written = File::create(&out)
+                .map_err(|e| e.to_string())
+                .and_then(|file| {
+                    serde_json::to_writer_pretty(std::io::BufWriter::new(file), &model)
+                        .map_err(|e| e.to_string())
+                });
+
+            if let Err(error) = written {
+                eprintln!("error: could not write {}: {error}", out.display());
+                return ExitCode::FAILURE;
+            }
+        }
+        Commands::Generate { model: path, seed } => {
+            let model: Model = match read_json(path.clone()) {
+                Ok(model) => model,
+                Err(error) => {
+                    eprintln!("{error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            if model.is_empty() {
+                eprintln!("error: {} was not trained on any words", path.display());
+                return ExitCode::FAILURE;
+            }
+
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            };
+
+            let out: Box<dyn Display> = match text_type {
+                TextType::Syllable => Box::new(model.syllable(&mut rng)),
+                TextType::Word => Box::new(model.word(&mut rng)),
+                TextType::Phrase => Box::new(model.phrase(&mut rng)),
+                TextType::Sentence => Box::new(model.sentence(&mut rng)),
+                TextType::Paragraph => Box::new(model.paragraph(&mut rng)),
+                TextType::Text => Box::new(model.text(&mut rng)),
+            };
+
+            println!("{out}");
+        }
+        Commands::Random { config, seed } => {
+            let mut config: wa::GeneratorConfig = match config.map(read_json).transpose() {
+                Ok(config) => config.unwrap_or_default(),
+                Err(error) => {
+                    eprintln!("{error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            if seed.is_some() {
+                config.seed = seed;
+            }
+            if let Some(phonotactics) = phonotactics {
+                config.phonotactics = phonotactics;
+            }
+
+            let mut generator = match wa::Generator::new(config) {
+                Ok(generator) => generator,
+                Err(error) => {
+                    eprintln!("error: {error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let out: Box<dyn Display> = match text_type {
+                TextType::Syllable => Box::new(generator.syllable()),
+                TextType::Word => Box::new(generator.word()),
+                TextType::Phrase => Box::new(generator.phrase()),
+                TextType::Sentence => Box::new(generator.sentence()),
+                TextType::Paragraph => Box::new(generator.paragraph()),
+                TextType::Text => Box::new(generator.text()),
+            };
+
+            println!("{out}");
+        }
+        #[cfg(feature = "synth")]
+        Commands::Speak { raw, out, voice } => {
+            let voice: wa::synth::Voice = match voice.map(read_json).transpose() {
+                Ok(voice) => voice.unwrap_or_default(),
+                Err(error) => {
+                    eprintln!("{error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let speech = match_text_type!(text_type, &raw, mode, rules, dyn wa::synth::Speak);
+            let samples = match speech.speak(&voice) {
+                Ok(samples) => samples,
+                Err(error) => {
+                    eprintln!("error: {error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let written = File::create(&out).and_then(|file| {
+                wa::synth::write_wav(std::io::BufWriter::new(file), &samples, voice.sample_rate)
+            });
+
+            if let Err(error) = written {
+                eprintln!("error: could not write {}: {error}", out.display());
+                return ExitCode::FAILURE;
+            }
         }
     }
+
+    ExitCode::SUCCESS
+}
+
+fn read_json<D: serde::de::DeserializeOwned>(path: PathBuf) -> Result<D, String> {
+    let file =
+        File::open(&path).map_err(|e| format!("error: could not open {}: {e}", path.display()))?;
+
+    serde_json::from_reader(BufReader::new(file))
+        .map_err(|e| format!("error: could not read {}: {e}", path.display()))
+}
+
+// a text file parsed whole, errors are rendered against the file
+fn read_text(
+    path: &Path,
+    mode: wa::ParseMode,
+    phonotactics: Option<&Phonotactics>,
+) -> Result<wa::Text, String> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|e| format!("error: could not open {}: {e}", path.display()))?;
+
+    parse(&raw, mode, phonotactics)
+        .map_err(|error| format!("{}: {}", path.display(), error.render(&raw).trim_end()))
+}
+
+fn read_sandhi(path: PathBuf) -> Result<wa::sandhi::Sandhi, String> {
+    let rules = std::fs::read_to_string(&path)
+        .map_err(|e| format!("error: could not open {}: {e}", path.display()))?;
+
+    rules
+        .parse()
+        .map_err(|e| format!("error: could not read {}: {e}", path.display()))
+}
+
+fn read_history(path: PathBuf) -> Result<History, String> {
+    let changes = std::fs::read_to_string(&path)
+        .map_err(|e| format!("error: could not open {}: {e}", path.display()))?;
+
+    changes
+        .parse()
+        .map_err(|e| format!("error: could not read {}: {e}", path.display()))
+}
+
+// parses like `wa::parse_with`, also rejecting anything the phonotactics forbid if there are any
+fn parse<N: wa::Parse + Check>(
+    raw: &str,
+    mode: wa::ParseMode,
+    phonotactics: Option<&Phonotactics>,
+) -> Result<N, wa::ParseError> {
+    match phonotactics {
+        Some(phonotactics) => phonotactics.parse(raw, mode),
+        None => wa::parse_with(raw, mode),
+    }
+}
+
+// prints the developed text, or the parse error and returns false
+fn develop<N: wa::Parse + Check + Develop + Display>(
+    raw: &str,
+    mode: wa::ParseMode,
+    phonotactics: Option<&Phonotactics>,
+    history: &History,
+    trace: bool,
+) -> bool {
+    let text = match parse::<N>(raw, mode, phonotactics) {
+        Ok(text) => text,
+        Err(error) => {
+            eprint!("{}", error.render(raw));
+            return false;
+        }
+    };
+
+    if trace {
+        for derivation in text.derivations(history) {
+            println!("{derivation}");
+        }
+
+        println!();
+    }
+
+    println!("{}", text.develop(history));
+
+    true
 }
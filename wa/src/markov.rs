@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+use serde_derive::*;
+
+use crate::{
+    generator::pick, serde::Compact, Paragraph, Phrase, Sentence, Syllable, Terminator, Text, Word,
+    T,
+};
+
+// a generator that imitates the texts it was trained on. a word's first syllable is drawn from the
+// syllables words start with, and every one after it from the syllables that followed the one
+// before. when a syllable was never followed by anything, the next is drawn from every syllable
+// and given a tone that followed the previous tone. every length is drawn from the lengths seen.
+// an empty model has nothing to imitate and panics when asked to
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Model {
+    pub starts: BTreeMap<Compact<Syllable>, usize>,
+    pub transitions: BTreeMap<Compact<Syllable>, BTreeMap<Compact<Syllable>, usize>>,
+    pub syllables: BTreeMap<Compact<Syllable>, usize>,
+    // tones that followed each other in a word
+    pub tones: BTreeMap<T, BTreeMap<T, usize>>,
+
+    // syllables in a word, words in a phrase, phrases in a sentence and so on
+    pub word_lengths: BTreeMap<usize, usize>,
+    pub phrase_lengths: BTreeMap<usize, usize>,
+    pub sentence_lengths: BTreeMap<usize, usize>,
+    pub paragraph_lengths: BTreeMap<usize, usize>,
+    pub text_lengths: BTreeMap<usize, usize>,
+
+    pub terminators: BTreeMap<Terminator, usize>,
+}
+
+fn count<K: Ord>(counts: &mut BTreeMap<K, usize>, key: K) {
+    *counts.entry(key).or_default() += 1;
+}
+
+// `None` if nothing was ever counted
+fn sample<K: Copy + Ord>(rng: &mut impl Rng, counts: &BTreeMap<K, usize>) -> Option<K> {
+    let weights: Vec<(K, f64)> = counts
+        .iter()
+        .filter(|(_, n)| **n > 0)
+        .map(|(key, n)| (*key, *n as f64))
+        .collect();
+
+    (!weights.is_empty()).then(|| pick(rng, &weights))
+}
+
+impl Model {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn trained_on<'a>(texts: impl IntoIterator<Item = &'a Text>) -> Self {
+        let mut model = Self::new();
+
+        for text in texts {
+            model.train(text);
+        }
+
+        model
+    }
+
+    // whether there is anything to generate words from. training fills both maps together, but a
+    // model read from JSON can have starts without syllables to follow them
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty() || self.syllables.is_empty()
+    }
+
+    pub fn train(&mut self, text: &Text) {
+        count(&mut self.text_lengths, text.len());
+
+        for paragraph in text.iter() {
+            self.train_paragraph(paragraph);
+        }
+    }
+
+    pub fn train_paragraph(&mut self, paragraph: &Paragraph) {
+        count(&mut self.paragraph_lengths, paragraph.len());
+
+        for sentence in paragraph.iter() {
+            self.train_sentence(sentence);
+        }
+    }
+
+    pub fn train_sentence(&mut self, sentence: &Sentence) {
+        count(&mut self.sentence_lengths, sentence.len());
+        count(&mut self.terminators, sentence.terminator);
+
+        for phrase in sentence.iter() {
+            self.train_phrase(phrase);
+        }
+    }
+
+    pub fn train_phrase(&mut self, phrase: &Phrase) {
+        count(&mut self.phrase_lengths, phrase.len());
+
+        for word in phrase.iter() {
+            self.train_word(word);
+        }
+    }
+
+    pub fn train_word(&mut self, word: &Word) {
+        count(&mut self.word_lengths, word.len());
+
+        let Some(first) = word.first() else {
+            return;
+        };
+
+        count(&mut self.starts, Compact(*first));
+
+        for syllable in word.iter() {
+            count(&mut self.syllables, Compact(*syllable));
+        }
+
+        for pair in word.windows(2) {
+            count(
+                self.transitions.entry(Compact(pair[0])).or_default(),
+                Compact(pair[1]),
+            );
+            count(self.tones.entry(pair[0].tone).or_default(), pair[1].tone);
+        }
+    }
+
+    // a level that was never seen has one item
+    fn length(rng: &mut impl Rng, lengths: &BTreeMap<usize, usize>) -> usize {
+        sample(rng, lengths).unwrap_or(1)
+    }
+
+    pub fn syllable(&self, rng: &mut impl Rng) -> Syllable {
+        *sample(rng, &self.syllables).expect("the model has been trained on some words")
+    }
+
+    pub fn syllable_after(&self, rng: &mut impl Rng, previous: &Syllable) -> Syllable {
+        if let Some(next) = self
+            .transitions
+            .get(&Compact(*previous))
+            .and_then(|next| sample(rng, next))
+        {
+            return *next;
+        }
+
+        let mut syllable = self.syllable(rng);
+
+        if let Some(tone) = self
+            .tones
+            .get(&previous.tone)
+            .and_then(|next| sample(rng, next))
+        {
+            syllable.tone = tone;
+        }
+
+        syllable
+    }
+
+    pub fn word(&self, rng: &mut impl Rng) -> Word {
+        let first = *sample(rng, &self.starts).expect("the model has been trained on some words");
+        self.word_from(rng, first)
+    }
+
+    // a word starting with `first`, which doesn't have to be one the model has seen
+    pub fn word_from(&self, rng: &mut impl Rng, first: Syllable) -> Word {
+        let length = Self::length(rng, &self.word_lengths).max(1);
+
+        let mut word = Vec::with_capacity(length);
+        word.push(first);
+
+        while word.len() < length {
+            let syllable = self.syllable_after(rng, word.last().unwrap());
+            word.push(syllable);
+        }
+
+        Word(word)
+    }
+
+    pub fn phrase(&self, rng: &mut impl Rng) -> Phrase {
+        let length = Self::length(rng, &self.phrase_lengths);
+
+        Phrase((0..length).map(|_| self.word(rng)).collect())
+    }
+
+    pub fn sentence(&self, rng: &mut impl Rng) -> Sentence {
+        let length = Self::length(rng, &self.sentence_lengths);
+
+        let mut sentence = Sentence::new((0..length).map(|_| self.phrase(rng)).collect());
+        sentence.terminator = sample(rng, &self.terminators).unwrap_or_default();
+
+        sentence
+    }
+
+    pub fn paragraph(&self, rng: &mut impl Rng) -> Paragraph {
+        let length = Self::length(rng, &self.paragraph_lengths);
+
+        Paragraph((0..length).map(|_| self.sentence(rng)).collect())
+    }
+
+    pub fn text(&self, rng: &mut impl Rng) -> Text {
+        let length = Self::length(rng, &self.text_lengths);
+
+        Text((0..length).map(|_| self.paragraph(rng)).collect())
+    }
+}
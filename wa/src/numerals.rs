@@ -0,0 +1,295 @@
+use std::{fmt::Display, str::FromStr, sync::OnceLock};
+
+use crate::{Parse, ParseMode, Phrase, Syllable, Word};
+
+// `value = word` gives a number its word, and `omit-one` leaves out a multiplier of one
+const STANDARD: &str = "
+# the digits, every number below the smallest power needs a word
+0 = ze~
+1 = ka,
+2 = tu`
+3 = sa^
+4 = pi~
+5 = lo,
+6 = bu^
+7 = si`
+8 = ho,
+9 = wa~
+
+# the powers, a number is the multiplier of the largest power it reaches, that power and whatever
+# is left over, so 1234 is pyo,h-tu`-ku`y-sa^-te^x-pi~
+10 = te^x
+100 = ku`y
+1000 = pyo,h
+1000000 = rwa^w
+1000000000 = syu~y
+
+# ten, not one ten
+omit-one
+";
+
+// the words numbers are spelled out with and how they are put together. every number has exactly
+// one spelling, so a word that isn't spelled the way `word` would spell it is not a number
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Numerals {
+    // the words for every number below the smallest power
+    digits: Vec<Word>,
+    // smallest first
+    powers: Vec<(u64, Word)>,
+    omit_one: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Unit {
+    Digit(u64),
+    Power(u64),
+}
+
+impl Numerals {
+    // the numerals behind `#1234` in parsed text
+    pub fn standard() -> &'static Numerals {
+        static STANDARD_NUMERALS: OnceLock<Numerals> = OnceLock::new();
+
+        STANDARD_NUMERALS.get_or_init(|| STANDARD.parse().expect("the standard numerals are valid"))
+    }
+
+    // the number as a single word
+    pub fn word(&self, n: u64) -> Word {
+        let mut syllables = Vec::new();
+        self.spell(n, &mut syllables);
+
+        Word(syllables)
+    }
+
+    // the number with a word for each power in it and one for the digit left over
+    pub fn phrase(&self, mut n: u64) -> Phrase {
+        let mut words = Vec::new();
+
+        loop {
+            let Some((power, word)) = self.largest_power(n) else {
+                if n > 0 || words.is_empty() {
+                    words.push(self.word(n));
+                }
+
+                break;
+            };
+
+            let mut group = Vec::new();
+            self.spell_multiplier(n / power, &mut group);
+            group.extend_from_slice(word);
+            words.push(Word(group));
+
+            n %= power;
+            if n == 0 {
+                break;
+            }
+        }
+
+        Phrase(words)
+    }
+
+    // the number spelled by the syllables, if they spell one
+    pub fn value(&self, syllables: &[Syllable]) -> Option<u64> {
+        let units = self.units(syllables)?;
+        let n = self.evaluate(&units)?;
+
+        // anything spelled differently, like one ten or two tens, is not a number
+        let mut spelling = Vec::new();
+        self.spell(n, &mut spelling);
+
+        (spelling == syllables).then_some(n)
+    }
+
+    pub fn word_value(&self, word: &Word) -> Option<u64> {
+        self.value(word)
+    }
+
+    pub fn phrase_value(&self, phrase: &Phrase) -> Option<u64> {
+        let syllables: Vec<Syllable> = phrase
+            .iter()
+            .flat_map(|word| word.iter().copied())
+            .collect();
+
+        // only the way `phrase` splits a number into words
+        self.value(&syllables)
+            .filter(|&n| self.phrase(n) == *phrase)
+    }
+
+    fn largest_power(&self, n: u64) -> Option<(u64, &Word)> {
+        self.powers
+            .iter()
+            .rev()
+            .find(|(power, _)| *power <= n)
+            .map(|(power, word)| (*power, word))
+    }
+
+    fn spell(&self, n: u64, out: &mut Vec<Syllable>) {
+        match self.largest_power(n) {
+            None => out.extend_from_slice(&self.digits[n as usize]),
+            Some((power, word)) => {
+                self.spell_multiplier(n / power, out);
+                out.extend_from_slice(word);
+
+                if !n.is_multiple_of(power) {
+                    self.spell(n % power, out);
+                }
+            }
+        }
+    }
+
+    fn spell_multiplier(&self, n: u64, out: &mut Vec<Syllable>) {
+        if n != 1 || !self.omit_one {
+            self.spell(n, out);
+        }
+    }
+
+    // splits the syllables into digits and powers, the longest word first
+    fn units(&self, mut syllables: &[Syllable]) -> Option<Vec<Unit>> {
+        let words = self
+            .digits
+            .iter()
+            .enumerate()
+            .map(|(digit, word)| (Unit::Digit(digit as u64), word))
+            .chain(
+                self.powers
+                    .iter()
+                    .map(|(power, word)| (Unit::Power(*power), word)),
+            );
+
+        let mut units = Vec::new();
+
+        while !syllables.is_empty() {
+            let (unit, word) = words
+                .clone()
+                .filter(|(_, word)| syllables.starts_with(word))
+                .max_by_key(|(_, word)| word.len())?;
+
+            units.push(unit);
+            syllables = &syllables[word.len()..];
+        }
+
+        Some(units)
+    }
+
+    // the largest power splits the units into its multiplier and what is left over
+    fn evaluate(&self, units: &[Unit]) -> Option<u64> {
+        let largest = units
+            .iter()
+            .enumerate()
+            .filter_map(|(i, unit)| match unit {
+                Unit::Power(power) => Some((i, *power)),
+                Unit::Digit(_) => None,
+            })
+            .max_by_key(|(_, power)| *power);
+
+        let Some((i, power)) = largest else {
+            return match units {
+                [Unit::Digit(digit)] => Some(*digit),
+                _ => None,
+            };
+        };
+
+        let multiplier = match &units[..i] {
+            [] if self.omit_one => 1,
+            [] => return None,
+            units => self.evaluate(units)?,
+        };
+
+        let rest = match &units[i + 1..] {
+            [] => 0,
+            units => self.evaluate(units)?,
+        };
+
+        multiplier.checked_mul(power)?.checked_add(rest)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NumeralsError {
+    // 1-based
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for NumeralsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for NumeralsError {}
+
+impl FromStr for Numerals {
+    type Err = NumeralsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words: Vec<(u64, Word)> = Vec::new();
+        let mut omit_one = false;
+        let mut last_line = 0;
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            last_line = i + 1;
+
+            let error = |message: String| NumeralsError {
+                line: i + 1,
+                message,
+            };
+
+            if line == "omit-one" {
+                omit_one = true;
+                continue;
+            }
+
+            let (value, word) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected '=' between a number and its word".to_owned()))?;
+
+            let value: u64 = value
+                .trim()
+                .parse()
+                .map_err(|_| error(format!("'{}' is not a number", value.trim())))?;
+            let word = Word::parse_with(word.trim(), ParseMode::Strict)
+                .map_err(|e| error(format!("'{}' is not a word: {e}", word.trim())))?;
+
+            if words.iter().any(|(v, _)| *v == value) {
+                return Err(error(format!("{value} already has a word")));
+            }
+            if let Some((other, _)) = words.iter().find(|(_, w)| *w == word) {
+                return Err(error(format!("{word} is already the word for {other}")));
+            }
+
+            words.push((value, word));
+        }
+
+        words.sort_by_key(|(value, _)| *value);
+
+        // the words from 0 on run up to the smallest power, the base, and the digits are the ones
+        // below it. everything after the run is a larger power
+        let run = words
+            .iter()
+            .enumerate()
+            .take_while(|(i, (value, _))| *value == *i as u64)
+            .count();
+
+        if run < 3 {
+            return Err(NumeralsError {
+                line: last_line,
+                message: "expected words for 0, 1 and the smallest power".to_owned(),
+            });
+        }
+
+        let powers = words.split_off(run - 1);
+
+        Ok(Numerals {
+            digits: words.into_iter().map(|(_, word)| word).collect(),
+            powers,
+            omit_one,
+        })
+    }
+}
@@ -0,0 +1,903 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Write},
+    ops::Range,
+};
+
+use unicode_normalization::char::is_combining_mark;
+
+use crate::{
+    numerals::Numerals,
+    syllabifier::{self, Segmentation},
+    Enclosure, Paragraph, Phrase, Sentence, Separator, Span, Syllable, Terminator, Text, Word, T,
+};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ParseMode {
+    // the whole input must be consumed
+    #[default]
+    Strict,
+    // anything after a syllable's coda is ignored, and so is an unknown coda
+    Lenient,
+}
+
+pub trait Parse: Sized {
+    fn parse_with(s: &str, mode: ParseMode) -> Result<Self, ParseError>;
+
+    // also returns where every node of the result came from in `s`
+    fn parse_spanned(s: &str, mode: ParseMode) -> Result<(Self, SourceMap), ParseError>;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Expected {
+    Onset,
+    Vowel,
+    Tone,
+    Coda,
+    End,
+    Punctuation,
+    // the digits after '#'
+    Number,
+    // the end of a quotation or parenthetical
+    Close(Enclosure),
+    EndOfInput,
+    Permitted,
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Expected::Onset => "an onset",
+            Expected::Vowel => "a vowel",
+            Expected::Tone => "a tone mark",
+            Expected::Coda => "a coda",
+            Expected::End => "the end of the syllable",
+            Expected::Punctuation => "':', ';', '.', '?' or '!'",
+            Expected::Number => "a number",
+            Expected::Close(Enclosure::Quotation) => "the end of the quotation",
+            Expected::Close(Enclosure::Parenthetical) => "the end of the parenthetical",
+            Expected::EndOfInput => "the end of the input",
+            Expected::Permitted => "a syllable the phonotactics permit",
+        };
+
+        f.write_str(str)
+    }
+}
+
+// the position of a node in the text tree, only the levels that were parsed are set
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Path {
+    pub paragraph: Option<usize>,
+    pub sentence: Option<usize>,
+    pub phrase: Option<usize>,
+    pub word: Option<usize>,
+    pub syllable: Option<usize>,
+}
+
+impl Path {
+    pub fn is_empty(&self) -> bool {
+        *self == Path::default()
+    }
+
+    pub fn paragraph(mut self, index: usize) -> Self {
+        self.paragraph = Some(index);
+        self
+    }
+
+    pub fn sentence(mut self, index: usize) -> Self {
+        self.sentence = Some(index);
+        self
+    }
+
+    pub fn phrase(mut self, index: usize) -> Self {
+        self.phrase = Some(index);
+        self
+    }
+
+    pub fn word(mut self, index: usize) -> Self {
+        self.word = Some(index);
+        self
+    }
+
+    pub fn syllable(mut self, index: usize) -> Self {
+        self.syllable = Some(index);
+        self
+    }
+
+    // how many levels deep the node is, the parsed node itself is at depth 0
+    pub fn depth(&self) -> usize {
+        [
+            self.paragraph,
+            self.sentence,
+            self.phrase,
+            self.word,
+            self.syllable,
+        ]
+        .iter()
+        .filter(|index| index.is_some())
+        .count()
+    }
+}
+
+// maps every node of a parsed tree to its byte range in the source, the parsed node itself is
+// at the empty path
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    spans: BTreeMap<Path, Range<usize>>,
+}
+
+impl SourceMap {
+    pub(crate) fn insert(&mut self, path: Path, span: Range<usize>) {
+        self.spans.insert(path, span);
+    }
+
+    pub fn span(&self, path: &Path) -> Option<Range<usize>> {
+        self.spans.get(path).cloned()
+    }
+
+    // in document order
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &Range<usize>)> {
+        self.spans.iter()
+    }
+
+    pub fn syllables(&self) -> impl Iterator<Item = (&Path, &Range<usize>)> {
+        self.iter().filter(|(path, _)| path.syllable.is_some())
+    }
+
+    // the deepest node whose source contains the byte at `offset`
+    pub fn node_at(&self, offset: usize) -> Option<Path> {
+        self.iter()
+            .filter(|(_, span)| span.contains(&offset))
+            .max_by_key(|(path, _)| path.depth())
+            .map(|(path, _)| *path)
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let levels = [
+            ("paragraph", self.paragraph),
+            ("sentence", self.sentence),
+            ("phrase", self.phrase),
+            ("word", self.word),
+            ("syllable", self.syllable),
+        ];
+
+        let mut first = true;
+        for (name, index) in levels {
+            if let Some(index) = index {
+                if first {
+                    first = false;
+                } else {
+                    f.write_str(", ")?;
+                }
+
+                // humans count from 1
+                write!(f, "{name} {}", index + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    // byte range into the original input
+    pub span: Range<usize>,
+    pub expected: Expected,
+    // `None` means the input ended
+    pub found: Option<char>,
+    pub path: Path,
+    // anything else worth telling, like which rule was broken
+    pub note: Option<Box<str>>,
+}
+
+impl ParseError {
+    pub(crate) fn new(span: Range<usize>, expected: Expected, found: Option<char>) -> Self {
+        Self {
+            span,
+            expected,
+            found,
+            path: Path::default(),
+            note: None,
+        }
+    }
+
+    pub(crate) fn shift(mut self, offset: usize) -> Self {
+        self.span = self.span.start + offset..self.span.end + offset;
+        self
+    }
+
+    pub(crate) fn with_path(mut self, f: impl FnOnce(&mut Path)) -> Self {
+        f(&mut self.path);
+        self
+    }
+
+    pub fn message(&self) -> String {
+        let found = match self.found {
+            Some(ch) if is_combining_mark(ch) => format!("'\u{25CC}{ch}' (U+{:04X})", ch as u32),
+            Some(ch) => format!("'{ch}'"),
+            None => "end of input".to_owned(),
+        };
+
+        let message = match (self.expected, self.found) {
+            // a digit is only a tone mark in place of one, so after a tone it's just out of place
+            (Expected::End, Some(ch)) if ch.is_ascii_digit() => format!("unexpected digit {found}"),
+            (Expected::End, Some(ch)) if T::from_mark(ch).is_some() => {
+                format!("doubled tone mark {found}")
+            }
+            (Expected::End, Some(ch)) if is_combining_mark(ch) => {
+                format!("stray combining mark {found}")
+            }
+            (Expected::Permitted, _) => "syllable not permitted by the phonotactics".to_owned(),
+            _ => format!("expected {}, found {found}", self.expected),
+        };
+
+        match &self.note {
+            Some(note) => format!("{message}, {note}"),
+            None => message,
+        }
+    }
+
+    // renders a caret-underlined diagnostic pointing into `source`, which must be the parsed input
+    pub fn render(&self, source: &str) -> String {
+        let start = self.span.start.min(source.len());
+
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |i| start + i);
+        let line = source[line_start..line_end].trim_end_matches('\r');
+        let line_number = source[..line_start].matches('\n').count() + 1;
+
+        // only underline the part of the span that is on this line
+        let end = self.span.end.clamp(start, line_start + line.len());
+
+        // combining marks take no room on screen
+        let width = |str: &str| str.chars().filter(|ch| !is_combining_mark(*ch)).count();
+        let mut column = width(&source[line_start..start]);
+
+        // a combining mark is drawn over the character before it
+        if source[start..].starts_with(is_combining_mark) {
+            column = column.saturating_sub(1);
+        }
+        let carets = width(&source[start..end]).max(1);
+
+        let gutter = " ".repeat(line_number.to_string().len());
+
+        let mut buffer = String::new();
+        writeln!(buffer, "error: {}", self.message()).unwrap();
+        write!(buffer, "{gutter}--> {line_number}:{}", column + 1).unwrap();
+        if !self.path.is_empty() {
+            write!(buffer, " ({})", self.path).unwrap();
+        }
+        writeln!(buffer).unwrap();
+        writeln!(buffer, "{gutter} |").unwrap();
+        writeln!(buffer, "{line_number} | {line}").unwrap();
+        writeln!(
+            buffer,
+            "{gutter} | {}{}",
+            " ".repeat(column),
+            "^".repeat(carets)
+        )
+        .unwrap();
+
+        buffer
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message())?;
+
+        if !self.path.is_empty() {
+            write!(f, " ({})", self.path)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum TokenKind {
+    // a run of letters, tone marks and combining marks, one or more syllables
+    Letters,
+    Hyphen,
+    Colon,
+    Semicolon,
+    Period,
+    Question,
+    Exclamation,
+    // '"' is one of these depending on what comes right before it, see `tokenize`
+    OpenQuote,
+    CloseQuote,
+    OpenParen,
+    CloseParen,
+    // '#' followed by digits spells out a number
+    Hash,
+    // any whitespace that is not a line break
+    Space,
+    Newline,
+    Other,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Token {
+    kind: TokenKind,
+    span: Range<usize>,
+}
+
+fn tokenize(src: &str) -> Vec<Token> {
+    let kind_of = |ch: char| match ch {
+        '-' => TokenKind::Hyphen,
+        ':' => TokenKind::Colon,
+        ';' => TokenKind::Semicolon,
+        '.' => TokenKind::Period,
+        '?' => TokenKind::Question,
+        '!' => TokenKind::Exclamation,
+        '“' => TokenKind::OpenQuote,
+        '”' => TokenKind::CloseQuote,
+        '(' => TokenKind::OpenParen,
+        ')' => TokenKind::CloseParen,
+        '#' => TokenKind::Hash,
+        '\n' => TokenKind::Newline,
+        ch if ch.is_whitespace() => TokenKind::Space,
+        ch if ch.is_alphanumeric() || is_combining_mark(ch) || T::from_mark(ch).is_some() => {
+            TokenKind::Letters
+        }
+        _ => TokenKind::Other,
+    };
+
+    let mut tokens: Vec<Token> = Vec::new();
+
+    for (i, ch) in src.char_indices() {
+        let end = i + ch.len_utf8();
+
+        // a '"' right after a word or a closing mark closes, anywhere else it opens. so `"a "b" c"`
+        // nests, and a closing '"' can't have a space before it
+        let kind = match ch {
+            '"' => match tokens.last().map(|token| token.kind) {
+                Some(TokenKind::Letters | TokenKind::CloseQuote | TokenKind::CloseParen) => {
+                    TokenKind::CloseQuote
+                }
+                _ => TokenKind::OpenQuote,
+            },
+            ch => kind_of(ch),
+        };
+
+        match tokens.last_mut() {
+            // runs of letters and of spaces make up a single token
+            Some(last)
+                if last.kind == kind && matches!(kind, TokenKind::Letters | TokenKind::Space) =>
+            {
+                last.span.end = end
+            }
+            _ => tokens.push(Token { kind, span: i..end }),
+        }
+    }
+
+    tokens
+}
+
+pub(crate) struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    mode: ParseMode,
+    // only a text is split into paragraphs by line breaks, anywhere else they are spaces
+    paragraphs: bool,
+    path: Path,
+    // the end of the last syllable or period, where the node being parsed ends so far
+    last_end: usize,
+    spans: Option<SourceMap>,
+    // quotations and parentheticals, only while parsing a sentence
+    enclosures: Option<Enclosures>,
+}
+
+#[derive(Default)]
+struct Enclosures {
+    // the words of the sentence so far
+    words: usize,
+    // where each enclosure that is still open started, innermost last
+    open: Vec<(Enclosure, usize)>,
+    spans: Vec<Span>,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(src: &'a str, mode: ParseMode) -> Self {
+        Self {
+            src,
+            tokens: tokenize(src),
+            pos: 0,
+            mode,
+            paragraphs: false,
+            path: Path::default(),
+            last_end: 0,
+            spans: None,
+            enclosures: None,
+        }
+    }
+
+    fn start(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.src.len(), |token| token.span.start)
+    }
+
+    // records the node at the current path as spanning from `start` to the last thing parsed
+    fn record(&mut self, start: usize) {
+        if let Some(spans) = &mut self.spans {
+            spans.insert(self.path, start..self.last_end.max(start));
+        }
+    }
+
+    fn peek(&self) -> Option<TokenKind> {
+        self.tokens.get(self.pos).map(|token| token.kind)
+    }
+
+    fn is_space(&self, kind: TokenKind) -> bool {
+        kind == TokenKind::Space || (kind == TokenKind::Newline && !self.paragraphs)
+    }
+
+    fn skip_space(&mut self) {
+        while self.peek().is_some_and(|kind| self.is_space(kind)) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_blank(&mut self) {
+        while self
+            .peek()
+            .is_some_and(|kind| matches!(kind, TokenKind::Space | TokenKind::Newline))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self, expected: Expected) -> ParseError {
+        let (span, found) = match self.tokens.get(self.pos) {
+            Some(token) => (
+                token.span.clone(),
+                self.src[token.span.clone()].chars().next(),
+            ),
+            None => (self.src.len()..self.src.len(), None),
+        };
+
+        ParseError {
+            span,
+            expected,
+            found,
+            path: self.path,
+            note: None,
+        }
+    }
+
+    // parses a whole input with `node`, allowing whitespace around it
+    pub(crate) fn parse<N>(
+        mut self,
+        node: impl FnOnce(&mut Self) -> Result<N, ParseError>,
+    ) -> Result<N, ParseError> {
+        self.skip_blank();
+        let node = node(&mut self)?;
+        self.skip_blank();
+
+        match self.peek() {
+            None => Ok(node),
+            Some(_) => Err(self.error(Expected::EndOfInput)),
+        }
+    }
+
+    pub(crate) fn parse_spanned<N>(
+        mut self,
+        node: impl FnOnce(&mut Self) -> Result<N, ParseError>,
+    ) -> Result<(N, SourceMap), ParseError> {
+        self.spans = Some(SourceMap::default());
+
+        let mut spans = None;
+        let node = self.parse(|parser| {
+            let node = node(parser);
+            spans = parser.spans.take();
+            node
+        })?;
+
+        Ok((node, spans.unwrap_or_default()))
+    }
+
+    pub(crate) fn syllable(&mut self) -> Result<Syllable, ParseError> {
+        match self.tokens.get(self.pos) {
+            Some(token) if token.kind == TokenKind::Letters => {
+                let span = token.span.clone();
+                self.pos += 1;
+                self.last_end = span.end;
+                self.record(span.start);
+
+                Syllable::parse_with(&self.src[span.clone()], self.mode).map_err(|e| {
+                    let mut e = e.shift(span.start).with_path(|path| *path = self.path);
+
+                    // the syllable ended because its token did, not the input
+                    if e.found.is_none() {
+                        e.found = self.src[span.end..].chars().next();
+                        e.span.end += e.found.map_or(0, char::len_utf8);
+                    }
+
+                    e
+                })
+            }
+            _ => Err(self.error(Expected::Onset)),
+        }
+    }
+
+    // every segmentation of the letters at `pos`, if there is one into more than one syllable
+    fn segmentations(&self, pos: usize) -> Option<(Range<usize>, Vec<Segmentation>)> {
+        let span = self
+            .tokens
+            .get(pos)
+            .filter(|token| token.kind == TokenKind::Letters)?
+            .span
+            .clone();
+
+        let segmentations = syllabifier::segmentations(&self.src[span.clone()]);
+
+        segmentations
+            .iter()
+            .any(|segmentation| segmentation.len() > 1)
+            .then_some((span, segmentations))
+    }
+
+    // the letters of `span` with a hyphen between the syllables
+    fn hyphenated(&self, span: &Range<usize>, segmentation: &Segmentation) -> String {
+        let mut hyphenated = String::new();
+
+        let mut first = true;
+        for (_, range) in segmentation {
+            if first {
+                first = false;
+            } else {
+                hyphenated.push('-');
+            }
+
+            hyphenated.push_str(&self.src[span.start + range.start..span.start + range.end]);
+        }
+
+        hyphenated
+    }
+
+    // one or more syllables. a lenient parse also splits letters written without hyphens, a strict
+    // one points out where the hyphens go
+    fn syllables(&mut self, word: &mut Vec<Syllable>) -> Result<(), ParseError> {
+        if self.peek() == Some(TokenKind::Hash) {
+            return self.numeral(word);
+        }
+
+        self.path.syllable = Some(word.len());
+
+        if self.mode == ParseMode::Lenient {
+            if let Some((span, segmentations)) = self.segmentations(self.pos) {
+                // letters never split more than one way, the syllabifier tests check it in
+                // `every_pair_of_syllables_splits_one_way`
+                debug_assert!(segmentations.len() <= 1);
+
+                self.pos += 1;
+
+                for (syllable, range) in segmentations.into_iter().flatten() {
+                    self.path.syllable = Some(word.len());
+                    self.last_end = span.start + range.end;
+                    self.record(span.start + range.start);
+                    word.push(syllable);
+                }
+
+                return Ok(());
+            }
+        }
+
+        let pos = self.pos;
+
+        let syllable = self.syllable().map_err(|mut error| {
+            let split = self.segmentations(pos).and_then(|(span, segmentations)| {
+                let segmentation = segmentations.into_iter().find(|s| s.len() > 1)?;
+                Some(self.hyphenated(&span, &segmentation))
+            });
+
+            if let Some(hyphenated) = split {
+                error.note =
+                    Some(format!("syllables are separated by hyphens, as in {hyphenated}").into());
+            }
+
+            error
+        })?;
+
+        word.push(syllable);
+
+        Ok(())
+    }
+
+    // '#' digits, spelled out with the standard numerals. every syllable of the number spans the
+    // whole escape
+    fn numeral(&mut self, word: &mut Vec<Syllable>) -> Result<(), ParseError> {
+        let start = self.start();
+        self.pos += 1;
+
+        let span = match self.tokens.get(self.pos) {
+            Some(token) if token.kind == TokenKind::Letters => token.span.clone(),
+            _ => return Err(self.error(Expected::Number)),
+        };
+        let digits = &self.src[span.clone()];
+
+        if let Some((i, ch)) = digits.char_indices().find(|(_, ch)| !ch.is_ascii_digit()) {
+            let at = span.start + i;
+
+            return Err(
+                ParseError::new(at..at + ch.len_utf8(), Expected::Number, Some(ch))
+                    .with_path(|path| *path = self.path),
+            );
+        }
+
+        let Ok(n) = digits.parse::<u64>() else {
+            let mut error = self.error(Expected::Number);
+            error.note = Some(format!("numbers go up to {}", u64::MAX).into());
+
+            return Err(error);
+        };
+
+        self.last_end = span.end;
+        self.pos += 1;
+
+        for syllable in Numerals::standard().word(n).0 {
+            self.path.syllable = Some(word.len());
+            self.record(start);
+            word.push(syllable);
+        }
+
+        Ok(())
+    }
+
+    // word := part (space? '-' space? part)*, part := syllable | '#' digits
+    pub(crate) fn word(&mut self) -> Result<Word, ParseError> {
+        let start = self.start();
+        let mut word = Vec::new();
+
+        loop {
+            self.syllables(&mut word)?;
+
+            let before = self.pos;
+            self.skip_space();
+
+            if self.peek() == Some(TokenKind::Hyphen) {
+                self.pos += 1;
+                self.skip_space();
+            } else {
+                self.pos = before;
+                break;
+            }
+        }
+
+        self.path.syllable = None;
+        self.record(start);
+
+        Ok(Word(word))
+    }
+
+    // opens any quotations and parentheticals before the next word
+    fn open_enclosures(&mut self) {
+        if self.enclosures.is_none() {
+            return;
+        }
+
+        loop {
+            let enclosure = match self.peek() {
+                Some(TokenKind::OpenQuote) => Enclosure::Quotation,
+                Some(TokenKind::OpenParen) => Enclosure::Parenthetical,
+                _ => break,
+            };
+
+            if let Some(enclosures) = &mut self.enclosures {
+                enclosures.open.push((enclosure, enclosures.words));
+            }
+
+            self.pos += 1;
+            self.skip_space();
+        }
+    }
+
+    // closes quotations and parentheticals after a word, innermost first
+    fn close_enclosures(&mut self) -> Result<(), ParseError> {
+        if self.enclosures.is_none() {
+            return Ok(());
+        }
+
+        loop {
+            let before = self.pos;
+            self.skip_space();
+
+            let closing = match self.peek() {
+                Some(TokenKind::CloseQuote) => Enclosure::Quotation,
+                Some(TokenKind::CloseParen) => Enclosure::Parenthetical,
+                _ => {
+                    self.pos = before;
+                    return Ok(());
+                }
+            };
+
+            let enclosures = self.enclosures.as_ref().expect("checked above");
+
+            match enclosures.open.last() {
+                Some((enclosure, _)) if *enclosure == closing => {}
+                Some((enclosure, _)) => return Err(self.error(Expected::Close(*enclosure))),
+                None => return Err(self.error(Expected::Punctuation)),
+            }
+
+            let enclosures = self.enclosures.as_mut().expect("checked above");
+            let (enclosure, start) = enclosures.open.pop().expect("checked above");
+
+            enclosures.spans.push(Span {
+                enclosure,
+                start,
+                end: enclosures.words,
+            });
+
+            self.last_end = self.tokens[self.pos].span.end;
+            self.pos += 1;
+        }
+    }
+
+    fn at_enclosure_open(&self) -> bool {
+        self.enclosures.is_some()
+            && matches!(
+                self.peek(),
+                Some(TokenKind::OpenQuote | TokenKind::OpenParen)
+            )
+    }
+
+    // phrase := word (space word)*, within a sentence words can be enclosed in quotes and
+    // parentheses
+    pub(crate) fn phrase(&mut self) -> Result<Phrase, ParseError> {
+        let start = self.start();
+        let mut phrase = Vec::new();
+
+        loop {
+            self.path.word = Some(phrase.len());
+
+            self.open_enclosures();
+            phrase.push(self.word()?);
+
+            if let Some(enclosures) = &mut self.enclosures {
+                enclosures.words += 1;
+            }
+            self.close_enclosures()?;
+
+            let before = self.pos;
+            self.skip_space();
+
+            if !matches!(self.peek(), Some(TokenKind::Letters | TokenKind::Hash))
+                && !self.at_enclosure_open()
+            {
+                self.pos = before;
+                break;
+            }
+        }
+
+        self.path.word = None;
+        self.record(start);
+
+        Ok(Phrase(phrase))
+    }
+
+    // sentence := phrase ((':' | ';') phrase)* ('.' | '?' | '!')?
+    pub(crate) fn sentence(&mut self) -> Result<Sentence, ParseError> {
+        let start = self.start();
+        let outer = self.enclosures.replace(Enclosures::default());
+
+        let mut phrases = Vec::new();
+        let mut separators = Vec::new();
+
+        loop {
+            self.path.phrase = Some(phrases.len());
+            phrases.push(self.phrase()?);
+
+            self.skip_space();
+
+            let separator = match self.peek() {
+                Some(TokenKind::Colon) => Separator::Phrase,
+                Some(TokenKind::Semicolon) => Separator::List,
+                _ => break,
+            };
+
+            separators.push(separator);
+            self.pos += 1;
+            self.skip_space();
+        }
+
+        self.path.phrase = None;
+
+        let terminator = match self.peek() {
+            Some(TokenKind::Period) => Some(Terminator::Statement),
+            Some(TokenKind::Question) => Some(Terminator::Question),
+            Some(TokenKind::Exclamation) => Some(Terminator::Exclamation),
+            _ => None,
+        };
+
+        let enclosures = std::mem::replace(&mut self.enclosures, outer).unwrap_or_default();
+        if let Some((enclosure, _)) = enclosures.open.last() {
+            return Err(self.error(Expected::Close(*enclosure)));
+        }
+
+        if terminator.is_some() {
+            self.last_end = self.tokens[self.pos].span.end;
+            self.pos += 1;
+        }
+
+        self.record(start);
+
+        let mut spans = enclosures.spans;
+        spans.sort_by_key(|span| (span.start, std::cmp::Reverse(span.end)));
+
+        // all phrase breaks are the same as none
+        if separators
+            .iter()
+            .all(|separator| *separator == Separator::Phrase)
+        {
+            separators.clear();
+        }
+
+        Ok(Sentence {
+            phrases,
+            separators,
+            terminator: terminator.unwrap_or_default(),
+            spans,
+        })
+    }
+
+    // paragraph := sentence+, a sentence is only left unterminated at the end
+    pub(crate) fn paragraph(&mut self) -> Result<Paragraph, ParseError> {
+        let start = self.start();
+        let mut paragraph = Vec::new();
+
+        loop {
+            self.path.sentence = Some(paragraph.len());
+            paragraph.push(self.sentence()?);
+
+            let terminated = matches!(
+                self.tokens[self.pos - 1].kind,
+                TokenKind::Period | TokenKind::Question | TokenKind::Exclamation
+            );
+
+            self.skip_space();
+
+            match self.peek() {
+                None | Some(TokenKind::Newline) => break,
+                Some(_) if terminated => {}
+                Some(_) => return Err(self.error(Expected::Punctuation)),
+            }
+        }
+
+        self.path.sentence = None;
+        self.record(start);
+
+        Ok(Paragraph(paragraph))
+    }
+
+    // text := blank* (paragraph (newline blank*)?)*
+    pub(crate) fn text(&mut self) -> Result<Text, ParseError> {
+        self.paragraphs = true;
+
+        let mut text = Vec::new();
+
+        self.skip_blank();
+        let start = self.start();
+
+        while self.peek().is_some() {
+            self.path.paragraph = Some(text.len());
+            text.push(self.paragraph()?);
+
+            self.skip_blank();
+        }
+
+        self.path.paragraph = None;
+        self.record(start);
+
+        Ok(Text(text))
+    }
+}
@@ -0,0 +1,393 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use crate::{
+    sandhi::{Notation, Phones, Transcribe},
+    CStem, Detail, Enclosure, Ipa, IpaOptions, Paragraph, Phrase, PureC, Sentence, Syllable,
+    Terminator, Text, Word, C, H, T, V,
+};
+
+// ASCII phonetic alphabets, for places that can't show IPA
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Alphabet {
+    #[default]
+    XSampa,
+    Kirshenbaum,
+}
+
+// the diacritics and modifier letters the IPA transcription uses
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Mark {
+    Aspirated,
+    Palatalized,
+    Apical,
+    Lowered,
+    Centralized,
+    NonSyllabic,
+    MoreRounded,
+    LessRounded,
+    Nasalized,
+    Long,
+    Unreleased,
+}
+
+impl Mark {
+    const ALL: [Mark; 11] = [
+        Mark::Aspirated,
+        Mark::Palatalized,
+        Mark::Apical,
+        Mark::Lowered,
+        Mark::Centralized,
+        Mark::NonSyllabic,
+        Mark::MoreRounded,
+        Mark::LessRounded,
+        Mark::Nasalized,
+        Mark::Long,
+        Mark::Unreleased,
+    ];
+
+    fn ipa(&self) -> char {
+        match self {
+            Mark::Aspirated => 'ʰ',
+            Mark::Palatalized => 'ʲ',
+            Mark::Apical => '\u{033A}',
+            Mark::Lowered => '\u{031E}',
+            Mark::Centralized => '\u{0308}',
+            Mark::NonSyllabic => '\u{032F}',
+            Mark::MoreRounded => '\u{0357}',
+            Mark::LessRounded => '\u{0351}',
+            Mark::Nasalized => '\u{0303}',
+            Mark::Long => 'ː',
+            Mark::Unreleased => '\u{031A}',
+        }
+    }
+
+    // the allophonic detail a broad transcription leaves out
+    fn is_narrow(&self) -> bool {
+        matches!(self, Mark::Apical | Mark::Lowered | Mark::Centralized)
+    }
+}
+
+const TONE_LETTERS: [char; 5] = ['˩', '˨', '˧', '˦', '˥'];
+
+impl Alphabet {
+    // `None` where the alphabet has no way to write the mark, Kirshenbaum has nothing for the
+    // apical, lowered, rounding and non-syllabic marks
+    fn mark(&self, mark: Mark) -> Option<&'static str> {
+        let spelling = match (self, mark) {
+            (Alphabet::XSampa, Mark::Aspirated) => "_h",
+            (Alphabet::XSampa, Mark::Palatalized) => "_j",
+            (Alphabet::XSampa, Mark::Apical) => "_a",
+            (Alphabet::XSampa, Mark::Lowered) => "_o",
+            (Alphabet::XSampa, Mark::Centralized) => "_\"",
+            (Alphabet::XSampa, Mark::NonSyllabic) => "_^",
+            (Alphabet::XSampa, Mark::MoreRounded) => "_O",
+            (Alphabet::XSampa, Mark::LessRounded) => "_c",
+            (Alphabet::XSampa, Mark::Nasalized) => "~",
+            (Alphabet::XSampa, Mark::Long) => ":",
+            (Alphabet::XSampa, Mark::Unreleased) => "_}",
+
+            (Alphabet::Kirshenbaum, Mark::Aspirated) => "<h>",
+            (Alphabet::Kirshenbaum, Mark::Palatalized) => ";",
+            (Alphabet::Kirshenbaum, Mark::Apical) => return None,
+            (Alphabet::Kirshenbaum, Mark::Lowered) => return None,
+            (Alphabet::Kirshenbaum, Mark::Centralized) => "\"",
+            (Alphabet::Kirshenbaum, Mark::NonSyllabic) => return None,
+            (Alphabet::Kirshenbaum, Mark::MoreRounded) => return None,
+            (Alphabet::Kirshenbaum, Mark::LessRounded) => return None,
+            (Alphabet::Kirshenbaum, Mark::Nasalized) => "~",
+            (Alphabet::Kirshenbaum, Mark::Long) => ":",
+            (Alphabet::Kirshenbaum, Mark::Unreleased) => "<unx>",
+        };
+
+        Some(spelling)
+    }
+
+    // r and ɹ, the only letters the alphabets don't spell like IPA
+    fn rhotic(&self, trill: bool) -> &'static str {
+        match (self, trill) {
+            (Alphabet::XSampa, true) => "r",
+            (Alphabet::XSampa, false) => "r\\",
+            (Alphabet::Kirshenbaum, true) => "r<trl>",
+            (Alphabet::Kirshenbaum, false) => "r",
+        }
+    }
+
+    fn segment(&self, letter: &str, marks: &[Mark], detail: Detail) -> String {
+        let mut buffer = letter.to_owned();
+
+        for mark in marks {
+            if detail == Detail::Broad && mark.is_narrow() {
+                continue;
+            }
+
+            buffer.extend(self.mark(*mark));
+        }
+
+        buffer
+    }
+
+    // a glide is a non-syllabic vowel, or w and j where that can't be written
+    fn glide(&self, vowel: &str, glide: &str, rounding: Mark, detail: Detail) -> String {
+        if detail == Detail::Broad || self.mark(Mark::NonSyllabic).is_none() {
+            glide.to_owned()
+        } else {
+            self.segment(vowel, &[Mark::NonSyllabic, rounding], detail)
+        }
+    }
+
+    // X-SAMPA has a diacritic for each level, Kirshenbaum has none so tones are Chao numbers
+    fn tone(&self, levels: &[usize]) -> String {
+        match self {
+            Alphabet::XSampa => levels
+                .iter()
+                .map(|level| ["_B", "_L", "_M", "_H", "_T"][*level])
+                .collect(),
+            Alphabet::Kirshenbaum => {
+                let digits: String = levels.iter().map(|level| (level + 1).to_string()).collect();
+
+                if levels.len() == 1 {
+                    digits.repeat(2)
+                } else {
+                    digits
+                }
+            }
+        }
+    }
+
+    fn levels(tone: T) -> Vec<usize> {
+        tone.chao_levels()
+            .into_iter()
+            .map(|level| level as usize - 1)
+            .collect()
+    }
+
+    // both use X-SAMPA's global rise and fall
+    pub(crate) fn terminator(&self, terminator: Terminator) -> &'static str {
+        match terminator {
+            Terminator::Statement => "",
+            Terminator::Question => "<R>",
+            Terminator::Exclamation => "<F>",
+        }
+    }
+
+    // X-SAMPA spends braces on vowels, so parentheticals are in parentheses. quotation marks are
+    // X-SAMPA's primary stress and Kirshenbaum's centralization, so quotations are between dollar
+    // signs, which neither uses
+    pub(crate) fn brackets(&self, enclosure: Enclosure) -> (&'static str, &'static str) {
+        match enclosure {
+            Enclosure::Quotation => ("$", "$"),
+            Enclosure::Parenthetical => ("(", ")"),
+        }
+    }
+
+    // a syllable's phones, spelled from its phonemes unless a sandhi rule changed them
+    pub(crate) fn phones(&self, syllable: &Syllable, phones: &Phones, detail: Detail) -> String {
+        let options = IpaOptions {
+            detail,
+            ..IpaOptions::narrow()
+        };
+        let plain = Phones::from(syllable);
+
+        let slot = |phones: &str, plain: &str, spelled: String| {
+            if phones == plain {
+                spelled
+            } else {
+                self.transliterate(phones)
+            }
+        };
+
+        let onset = syllable.onset.phonetic_with(*self, &options);
+
+        let mut vowel = syllable.vowel.phonetic_with(*self, &options);
+        if syllable.tone.is_nasal() {
+            vowel.push_str(&syllable.tone.phonetic_with(*self, &options));
+        }
+
+        let coda = syllable
+            .coda
+            .map(|coda| coda.phonetic_with(*self, &options))
+            .unwrap_or_default();
+
+        let tone = self.tone(&Self::levels(syllable.tone));
+
+        let mut buffer = String::new();
+        buffer.push_str(&slot(&phones.onset, &plain.onset, onset));
+        buffer.push_str(&slot(&phones.vowel, &plain.vowel, vowel));
+        buffer.push_str(&slot(&phones.coda, &plain.coda, coda));
+        buffer.push_str(&slot(&phones.tone, &plain.tone, tone));
+
+        buffer
+    }
+
+    // respells whatever IPA a sandhi rule writes, a diacritic this alphabet has no spelling for is
+    // dropped and any other character that isn't ASCII becomes '?'
+    pub fn transliterate(&self, ipa: &str) -> String {
+        let ipa: String = ipa.nfd().collect();
+        let glides = [H::W, H::Y].map(|glide| (glide.ipa().nfd().collect::<String>(), glide));
+
+        let mut rest = ipa.as_str();
+        let mut buffer = String::new();
+        let mut levels = Vec::new();
+
+        while let Some(ch) = rest.chars().next() {
+            if let Some(level) = TONE_LETTERS.iter().position(|letter| *letter == ch) {
+                levels.push(level);
+                rest = &rest[ch.len_utf8()..];
+                continue;
+            }
+
+            if !levels.is_empty() {
+                buffer.push_str(&self.tone(&levels));
+                levels.clear();
+            }
+
+            if let Some((spelling, glide)) = glides
+                .iter()
+                .find(|(spelling, _)| rest.starts_with(spelling.as_str()))
+            {
+                buffer.push_str(&glide.phonetic(*self));
+                rest = &rest[spelling.len()..];
+                continue;
+            }
+
+            match ch {
+                'r' => buffer.push_str(self.rhotic(true)),
+                'ɹ' => buffer.push_str(self.rhotic(false)),
+                '↗' => buffer.push_str(self.terminator(Terminator::Question)),
+                '↘' => buffer.push_str(self.terminator(Terminator::Exclamation)),
+                ch if ch.is_ascii() => buffer.push(ch),
+                ch => match Mark::ALL.iter().find(|mark| mark.ipa() == ch) {
+                    Some(mark) => buffer.extend(self.mark(*mark)),
+                    None if is_combining_mark(ch) => {}
+                    None => buffer.push('?'),
+                },
+            }
+
+            rest = &rest[ch.len_utf8()..];
+        }
+
+        if !levels.is_empty() {
+            buffer.push_str(&self.tone(&levels));
+        }
+
+        buffer
+    }
+}
+
+// the IPA transcription in an ASCII alphabet. every phoneme is spelled on its own and everything
+// above them is put together the way the IPA is
+pub trait Phonetic {
+    // only the detail, sandhi rules and delimiting of `options` apply
+    fn phonetic_with(&self, alphabet: Alphabet, options: &IpaOptions) -> String;
+
+    fn phonetic(&self, alphabet: Alphabet) -> String {
+        self.phonetic_with(alphabet, &IpaOptions::narrow())
+    }
+
+    fn x_sampa(&self) -> String {
+        self.phonetic(Alphabet::XSampa)
+    }
+
+    fn kirshenbaum(&self) -> String {
+        self.phonetic(Alphabet::Kirshenbaum)
+    }
+}
+
+impl Phonetic for PureC {
+    fn phonetic_with(&self, alphabet: Alphabet, options: &IpaOptions) -> String {
+        use Mark::{Apical, Aspirated, Palatalized};
+
+        let (letter, marks): (&str, &[Mark]) = match self {
+            PureC::Strong(CStem::P) => ("p", &[Aspirated]),
+            PureC::Blunt(CStem::P) => ("b", &[]),
+            PureC::Sharp(CStem::P) => ("p", &[Palatalized]),
+
+            PureC::Strong(CStem::T) => ("t", &[Apical, Aspirated]),
+            PureC::Blunt(CStem::T) => ("d", &[Apical]),
+            PureC::Sharp(CStem::T) => ("t", &[Apical, Palatalized]),
+
+            PureC::Strong(CStem::K) => ("k", &[Aspirated]),
+            PureC::Blunt(CStem::K) => ("g", &[]),
+            PureC::Sharp(CStem::K) => ("k", &[Palatalized]),
+
+            PureC::Strong(CStem::S) => ("s", &[Apical]),
+            PureC::Blunt(CStem::S) => ("z", &[Apical]),
+            PureC::Sharp(CStem::S) => ("s", &[Palatalized]),
+
+            PureC::Strong(CStem::R) => (alphabet.rhotic(true), &[]),
+            PureC::Blunt(CStem::R) => (alphabet.rhotic(false), &[]),
+            PureC::Sharp(CStem::R) => ("l", &[]),
+        };
+
+        alphabet.segment(letter, marks, options.detail)
+    }
+}
+
+impl Phonetic for H {
+    fn phonetic_with(&self, alphabet: Alphabet, options: &IpaOptions) -> String {
+        match self {
+            H::W => alphabet.glide("u", "w", Mark::MoreRounded, options.detail),
+            H::Y => alphabet.glide("i", "j", Mark::LessRounded, options.detail),
+            H::X => "x".to_owned(),
+            H::H => "h".to_owned(),
+        }
+    }
+}
+
+impl Phonetic for C {
+    fn phonetic_with(&self, alphabet: Alphabet, options: &IpaOptions) -> String {
+        match self {
+            C::H(h) => h.phonetic_with(alphabet, options),
+            C::C(c) => c.phonetic_with(alphabet, options),
+        }
+    }
+}
+
+impl Phonetic for V {
+    fn phonetic_with(&self, alphabet: Alphabet, options: &IpaOptions) -> String {
+        let (letter, marks): (&str, &[Mark]) = match self {
+            V::A => ("a", &[Mark::Centralized]),
+            V::E => ("e", &[Mark::Lowered]),
+            V::I => ("i", &[]),
+            V::O => ("o", &[Mark::Lowered]),
+            V::U => ("u", &[]),
+        };
+
+        alphabet.segment(letter, marks, options.detail)
+    }
+}
+
+// like `Ipa`, a nasal tone on its own is the nasalisation unless its pitch is asked for
+impl Phonetic for T {
+    fn phonetic_with(&self, alphabet: Alphabet, options: &IpaOptions) -> String {
+        let pitch = alphabet.tone(&Alphabet::levels(*self));
+
+        match self {
+            T::Nasal => {
+                let mut buffer = alphabet.segment("", &[Mark::Nasalized], options.detail);
+
+                if options.nasal_pitch {
+                    buffer.push_str(&pitch);
+                }
+
+                buffer
+            }
+            _ => pitch,
+        }
+    }
+}
+
+macro_rules! impl_phonetic {
+    ($($ty:ty),*) => {
+        $(
+            impl Phonetic for $ty {
+                fn phonetic_with(&self, alphabet: Alphabet, options: &IpaOptions) -> String {
+                    let notation = Notation::Phonetic(alphabet, options.detail);
+                    options.delimited(&self.transcribe_in(options.sandhi(), notation))
+                }
+            }
+        )*
+    };
+}
+
+impl_phonetic!(Syllable, Word, Phrase, Sentence, Paragraph, Text);
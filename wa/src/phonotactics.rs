@@ -0,0 +1,232 @@
+use serde_derive::*;
+
+use crate::{
+    Expected, Paragraph, Parse, ParseError, ParseMode, Path, Phrase, Sentence, Syllable, Text,
+    Word, C, H, T, V,
+};
+
+// a missing component matches anything
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Pattern {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onset: Option<Vec<C>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vowel: Option<Vec<V>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tone: Option<Vec<T>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coda: Option<Vec<Option<H>>>,
+}
+
+impl Pattern {
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    pub fn onset(mut self, onsets: impl IntoIterator<Item = C>) -> Self {
+        self.onset = Some(onsets.into_iter().collect());
+        self
+    }
+
+    pub fn vowel(mut self, vowels: impl IntoIterator<Item = V>) -> Self {
+        self.vowel = Some(vowels.into_iter().collect());
+        self
+    }
+
+    pub fn tone(mut self, tones: impl IntoIterator<Item = T>) -> Self {
+        self.tone = Some(tones.into_iter().collect());
+        self
+    }
+
+    pub fn coda(mut self, codas: impl IntoIterator<Item = Option<H>>) -> Self {
+        self.coda = Some(codas.into_iter().collect());
+        self
+    }
+
+    pub fn matches(&self, syllable: &Syllable) -> bool {
+        fn contains<X: PartialEq>(set: &Option<Vec<X>>, x: &X) -> bool {
+            set.as_ref().is_none_or(|set| set.contains(x))
+        }
+
+        contains(&self.onset, &syllable.onset)
+            && contains(&self.vowel, &syllable.vowel)
+            && contains(&self.tone, &syllable.tone)
+            && contains(&self.coda, &syllable.coda)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Constraint {
+    // no syllable may match
+    Syllable(Pattern),
+    // within a word, no syllable matching the first pattern may be followed by one matching the second
+    Sequence(Pattern, Pattern),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Rule {
+    pub name: String,
+    pub forbid: Constraint,
+}
+
+impl Rule {
+    pub fn new(name: impl Into<String>, forbid: Constraint) -> Self {
+        Self {
+            name: name.into(),
+            forbid,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Violation {
+    pub rule: String,
+    // the (first) offending syllable, relative to the checked node
+    pub path: Path,
+    // how many syllables of the word, starting at `path`, break the rule together
+    pub syllables: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Phonotactics {
+    pub rules: Vec<Rule>,
+}
+
+impl Phonotactics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    // the first rule that `next` breaks, given the syllable before it in its word
+    pub fn forbids(&self, previous: Option<&Syllable>, next: &Syllable) -> Option<&Rule> {
+        self.rules.iter().find(|rule| match &rule.forbid {
+            Constraint::Syllable(pattern) => pattern.matches(next),
+            Constraint::Sequence(first, second) => {
+                previous.is_some_and(|previous| first.matches(previous)) && second.matches(next)
+            }
+        })
+    }
+
+    pub fn allows(&self, previous: Option<&Syllable>, next: &Syllable) -> bool {
+        self.forbids(previous, next).is_none()
+    }
+
+    pub fn check<N: Check + ?Sized>(&self, node: &N) -> Result<(), Violation> {
+        match node.violations(self).into_iter().next() {
+            Some(violation) => Err(violation),
+            None => Ok(()),
+        }
+    }
+
+    // parses like `wa::parse_with`, but also rejects anything these rules forbid
+    pub fn parse<L>(&self, input: &str, mode: ParseMode) -> Result<L, ParseError>
+    where
+        L: Parse + Check,
+    {
+        let (node, spans) = L::parse_spanned(input, mode)?;
+
+        match self.check(&node) {
+            Ok(()) => Ok(node),
+            Err(violation) => {
+                let mut last = violation.path;
+                if let Some(first) = violation.path.syllable {
+                    last.syllable = Some(first + violation.syllables - 1);
+                }
+
+                let start = spans.span(&violation.path).map_or(0, |span| span.start);
+                let end = spans.span(&last).map_or(start, |span| span.end);
+
+                let mut error = ParseError::new(
+                    start..end,
+                    Expected::Permitted,
+                    input[start..].chars().next(),
+                );
+                error.path = violation.path;
+                error.note = Some(format!("rule '{}' forbids it", violation.rule).into());
+
+                Err(error)
+            }
+        }
+    }
+}
+
+pub trait Check {
+    fn violations(&self, phonotactics: &Phonotactics) -> Vec<Violation>;
+}
+
+impl Check for Syllable {
+    fn violations(&self, phonotactics: &Phonotactics) -> Vec<Violation> {
+        phonotactics
+            .forbids(None, self)
+            .map(|rule| Violation {
+                rule: rule.name.clone(),
+                path: Path::default(),
+                syllables: 1,
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+impl Check for Word {
+    fn violations(&self, phonotactics: &Phonotactics) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for (i, syllable) in self.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|i| &self[i]);
+
+            for rule in &phonotactics.rules {
+                let syllables = match &rule.forbid {
+                    Constraint::Syllable(pattern) if pattern.matches(syllable) => 1,
+                    Constraint::Sequence(first, second)
+                        if previous.is_some_and(|previous| first.matches(previous))
+                            && second.matches(syllable) =>
+                    {
+                        2
+                    }
+                    _ => continue,
+                };
+
+                violations.push(Violation {
+                    rule: rule.name.clone(),
+                    path: Path::default().syllable(i + 1 - syllables),
+                    syllables,
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+macro_rules! impl_check {
+    ($ty:ty, $level:ident) => {
+        impl Check for $ty {
+            fn violations(&self, phonotactics: &Phonotactics) -> Vec<Violation> {
+                self.iter()
+                    .enumerate()
+                    .flat_map(|(i, child)| {
+                        child
+                            .violations(phonotactics)
+                            .into_iter()
+                            .map(move |mut violation| {
+                                violation.path.$level = Some(i);
+                                violation
+                            })
+                    })
+                    .collect()
+            }
+        }
+    };
+}
+
+impl_check!(Phrase, word);
+impl_check!(Sentence, phrase);
+impl_check!(Paragraph, sentence);
+impl_check!(Text, paragraph);
@@ -0,0 +1,220 @@
+use std::fmt::{self, Formatter, Write};
+
+use unicode_normalization::char::compose;
+
+use crate::{Enclosure, Paragraph, Phrase, Sentence, Syllable, Text, Word, T};
+
+// how a syllable is spelled, words and everything above them are always joined the same way. every
+// scheme here is read back by the parser
+pub trait Romanization {
+    fn write_syllable(&self, syllable: &Syllable, out: &mut dyn Write) -> fmt::Result;
+
+    // the opening and closing quotation marks, they can be the same since the parser tells them
+    // apart by what comes right before
+    fn quotes(&self) -> (char, char) {
+        ('“', '”')
+    }
+}
+
+// xúy, the tone is composed into the vowel where Unicode has a letter for it
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Unicode;
+
+// xu,y, what `{:#}` writes
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Ascii;
+
+// xu1y, with tones numbered 1 high, 2 low, 3 peaking and 4 nasal
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Numbered;
+
+impl Romanization for Unicode {
+    fn write_syllable(&self, syllable: &Syllable, out: &mut dyn Write) -> fmt::Result {
+        write!(out, "{}", syllable.onset)?;
+
+        let v = syllable.vowel.to_string().chars().next().unwrap();
+        let t = syllable.tone.to_string().chars().next().unwrap();
+
+        if let Some(ch) = compose(v, t) {
+            out.write_char(ch)?
+        } else {
+            out.write_char(v)?;
+            out.write_char(t)?;
+        }
+
+        if let Some(coda) = &syllable.coda {
+            write!(out, "{coda}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Romanization for Ascii {
+    fn write_syllable(&self, syllable: &Syllable, out: &mut dyn Write) -> fmt::Result {
+        write!(
+            out,
+            "{}{}{:#}",
+            syllable.onset, syllable.vowel, syllable.tone
+        )?;
+
+        if let Some(coda) = &syllable.coda {
+            write!(out, "{coda}")?;
+        }
+
+        Ok(())
+    }
+
+    fn quotes(&self) -> (char, char) {
+        ('"', '"')
+    }
+}
+
+impl Romanization for Numbered {
+    fn write_syllable(&self, syllable: &Syllable, out: &mut dyn Write) -> fmt::Result {
+        let number = match syllable.tone {
+            T::High => '1',
+            T::Low => '2',
+            T::Peaking => '3',
+            T::Nasal => '4',
+        };
+
+        write!(out, "{}{}{number}", syllable.onset, syllable.vowel)?;
+
+        if let Some(coda) = &syllable.coda {
+            write!(out, "{coda}")?;
+        }
+
+        Ok(())
+    }
+
+    fn quotes(&self) -> (char, char) {
+        ('"', '"')
+    }
+}
+
+// the scheme `Display` uses, `{:#}` asks for ASCII
+pub(crate) fn for_formatter(f: &Formatter<'_>) -> &'static dyn Romanization {
+    if f.alternate() {
+        &Ascii
+    } else {
+        &Unicode
+    }
+}
+
+pub trait Romanize {
+    fn romanize_into(&self, scheme: &dyn Romanization, out: &mut dyn Write) -> fmt::Result;
+
+    fn romanize(&self, scheme: &dyn Romanization) -> String {
+        let mut buffer = String::new();
+
+        self.romanize_into(scheme, &mut buffer)
+            .expect("writing to a String can't fail");
+
+        buffer
+    }
+}
+
+impl Romanize for Syllable {
+    fn romanize_into(&self, scheme: &dyn Romanization, out: &mut dyn Write) -> fmt::Result {
+        scheme.write_syllable(self, out)
+    }
+}
+
+impl Romanize for Word {
+    fn romanize_into(&self, scheme: &dyn Romanization, out: &mut dyn Write) -> fmt::Result {
+        let mut first = true;
+        for syllable in self.iter() {
+            if first {
+                first = false;
+            } else {
+                out.write_char('-')?;
+            }
+
+            scheme.write_syllable(syllable, out)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Romanize for Phrase {
+    fn romanize_into(&self, scheme: &dyn Romanization, out: &mut dyn Write) -> fmt::Result {
+        let mut first = true;
+        for word in self.iter() {
+            if first {
+                first = false;
+            } else {
+                out.write_char(' ')?;
+            }
+
+            word.romanize_into(scheme, out)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Romanize for Sentence {
+    fn romanize_into(&self, scheme: &dyn Romanization, out: &mut dyn Write) -> fmt::Result {
+        let (open_quote, close_quote) = scheme.quotes();
+
+        let mut words = 0;
+        for (i, phrase) in self.iter().enumerate() {
+            if i > 0 {
+                write!(out, "{} ", self.separator(i).mark())?;
+            }
+
+            let mut first = true;
+            for word in phrase.iter() {
+                if first {
+                    first = false;
+                } else {
+                    out.write_char(' ')?;
+                }
+
+                for span in self.spans.iter().filter(|span| span.start == words) {
+                    match span.enclosure {
+                        Enclosure::Quotation => out.write_char(open_quote)?,
+                        Enclosure::Parenthetical => out.write_char('(')?,
+                    }
+                }
+
+                word.romanize_into(scheme, out)?;
+                words += 1;
+
+                // inner spans close first
+                for span in self.spans.iter().rev().filter(|span| span.end == words) {
+                    match span.enclosure {
+                        Enclosure::Quotation => out.write_char(close_quote)?,
+                        Enclosure::Parenthetical => out.write_char(')')?,
+                    }
+                }
+            }
+        }
+
+        out.write_char(self.terminator.mark())
+    }
+}
+
+impl Romanize for Paragraph {
+    fn romanize_into(&self, scheme: &dyn Romanization, out: &mut dyn Write) -> fmt::Result {
+        for sentence in self.iter() {
+            sentence.romanize_into(scheme, out)?;
+            out.write_char(' ')?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Romanize for Text {
+    fn romanize_into(&self, scheme: &dyn Romanization, out: &mut dyn Write) -> fmt::Result {
+        for paragraph in self.iter() {
+            paragraph.romanize_into(scheme, out)?;
+            out.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+}
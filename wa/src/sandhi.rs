@@ -0,0 +1,536 @@
+use std::{fmt::Display, str::FromStr, sync::OnceLock};
+
+use crate::{
+    phonotactics::Pattern, Alphabet, Detail, Enclosure, Ipa, Paragraph, Phrase, Sentence,
+    Separator, Syllable, Terminator, Text, Word, C, H, T, V,
+};
+
+// the default rules, in the same notation custom rules are loaded from:
+//
+//   [word|phrase] name: left + right -> change, change...
+//
+// `left` and `right` are patterns of terms like `onset=p|b|py`, `vowel=a`, `tone=peaking` and
+// `coda=h|none`, `_` matches any syllable. a change is `left.coda = p̚` to replace what a slot of
+// either syllable is pronounced as, or `right.onset += ː` to add to it. word rules only apply
+// within a word, phrase rules also apply across the words of a phrase. `#` starts a comment and `,`
+// separates changes, and neither has an escape, so what a change writes can't contain them
+const STANDARD: &str = "
+# a peaking tone before another peaking tone only rises
+phrase peaking-before-peaking: tone=peaking + tone=peaking -> left.tone = ˧˥
+
+# h before a plosive is an unreleased stop at the plosive's place
+phrase h-before-labial: coda=h + onset=p|b|py -> left.coda = p̚
+phrase h-before-dental: coda=h + onset=t|d|ty -> left.coda = t̺̚
+phrase h-before-velar: coda=h + onset=k|g|ky -> left.coda = k̚
+
+# a glide coda merges into the same glide as the next onset, which is held longer
+phrase w-before-w: coda=w + onset=w -> left.coda = , right.onset += ː
+phrase y-before-y: coda=y + onset=y -> left.coda = , right.onset += ː
+";
+
+// how each part of a syllable is pronounced, `Syllable::ipa` is these in order
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Phones {
+    pub onset: String,
+    // includes the nasalisation of a nasal tone
+    pub vowel: String,
+    pub coda: String,
+    pub tone: String,
+}
+
+impl From<&Syllable> for Phones {
+    fn from(syllable: &Syllable) -> Self {
+        let mut vowel = syllable.vowel.ipa();
+        if syllable.tone.is_nasal() {
+            vowel.push('\u{0303}');
+        }
+
+        Self {
+            onset: syllable.onset.ipa(),
+            vowel,
+            coda: syllable.coda.map(|coda| coda.ipa()).unwrap_or_default(),
+            tone: syllable.tone.chao_letters(),
+        }
+    }
+}
+
+impl Display for Phones {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.onset)?;
+        f.write_str(&self.vowel)?;
+        f.write_str(&self.coda)?;
+        f.write_str(&self.tone)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    Word,
+    Phrase,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Slot {
+    Onset,
+    Vowel,
+    Coda,
+    Tone,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Edit {
+    Replace(String),
+    Append(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Change {
+    pub side: Side,
+    pub slot: Slot,
+    pub edit: Edit,
+}
+
+impl Change {
+    fn apply(&self, left: &mut Phones, right: &mut Phones) {
+        let phones = match self.side {
+            Side::Left => left,
+            Side::Right => right,
+        };
+
+        let slot = match self.slot {
+            Slot::Onset => &mut phones.onset,
+            Slot::Vowel => &mut phones.vowel,
+            Slot::Coda => &mut phones.coda,
+            Slot::Tone => &mut phones.tone,
+        };
+
+        match &self.edit {
+            Edit::Replace(to) => *slot = to.clone(),
+            Edit::Append(suffix) => slot.push_str(suffix),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SandhiRule {
+    pub name: String,
+    pub scope: Scope,
+    pub left: Pattern,
+    pub right: Pattern,
+    pub changes: Vec<Change>,
+}
+
+// rules are matched against the phonemic syllables, not what earlier rules made of them, so they
+// never feed each other. when several rules change the same slot the last one wins
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Sandhi {
+    pub rules: Vec<SandhiRule>,
+}
+
+impl Sandhi {
+    // no rules, a broad transcription
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    // the rules behind the `Ipa` impls
+    pub fn standard() -> &'static Sandhi {
+        static STANDARD_RULES: OnceLock<Sandhi> = OnceLock::new();
+
+        STANDARD_RULES.get_or_init(|| {
+            STANDARD
+                .parse()
+                .expect("the standard sandhi rules are valid")
+        })
+    }
+
+    pub fn rule(mut self, rule: SandhiRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    fn apply(
+        &self,
+        boundary: Scope,
+        (left, left_phones): (&Syllable, &mut Phones),
+        (right, right_phones): (&Syllable, &mut Phones),
+    ) {
+        for rule in &self.rules {
+            if boundary == Scope::Phrase && rule.scope == Scope::Word {
+                continue;
+            }
+
+            if rule.left.matches(left) && rule.right.matches(right) {
+                for change in &rule.changes {
+                    change.apply(left_phones, right_phones);
+                }
+            }
+        }
+    }
+
+    // the phones of every syllable of the word
+    pub fn word_phones(&self, word: &Word) -> Vec<Phones> {
+        let mut phones: Vec<Phones> = word.iter().map(Phones::from).collect();
+
+        for i in 1..phones.len() {
+            let (before, after) = phones.split_at_mut(i);
+
+            self.apply(
+                Scope::Word,
+                (&word[i - 1], &mut before[i - 1]),
+                (&word[i], &mut after[0]),
+            );
+        }
+
+        phones
+    }
+
+    // the phones of every word of the phrase
+    pub fn phrase_phones(&self, phrase: &Phrase) -> Vec<Vec<Phones>> {
+        let mut words: Vec<Vec<Phones>> =
+            phrase.iter().map(|word| self.word_phones(word)).collect();
+
+        for i in 1..words.len() {
+            let (Some(left), Some(right)) = (phrase[i - 1].last(), phrase[i].first()) else {
+                continue;
+            };
+
+            let (before, after) = words.split_at_mut(i);
+
+            if let (Some(left_phones), Some(right_phones)) =
+                (before[i - 1].last_mut(), after[0].first_mut())
+            {
+                self.apply(Scope::Phrase, (left, left_phones), (right, right_phones));
+            }
+        }
+
+        words
+    }
+}
+
+// what a transcription is written in, a phonetic alphabet spells each phoneme itself and only
+// transliterates what a sandhi rule wrote
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Notation {
+    #[default]
+    Ipa,
+    Phonetic(Alphabet, Detail),
+}
+
+impl Notation {
+    fn phones(&self, syllable: &Syllable, phones: &Phones) -> String {
+        match self {
+            Notation::Ipa => phones.to_string(),
+            Notation::Phonetic(alphabet, detail) => alphabet.phones(syllable, phones, *detail),
+        }
+    }
+
+    fn terminator(&self, terminator: Terminator) -> &'static str {
+        match (self, terminator) {
+            (_, Terminator::Statement) => "",
+            (Notation::Ipa, Terminator::Question) => "↗",
+            (Notation::Ipa, Terminator::Exclamation) => "↘",
+            (Notation::Phonetic(alphabet, _), terminator) => alphabet.terminator(terminator),
+        }
+    }
+
+    // quotations are in quotation marks and parentheticals in braces, the way extIPA marks off a
+    // stretch of speech, since parentheses already mean a silent articulation
+    fn brackets(&self, enclosure: Enclosure) -> (&'static str, &'static str) {
+        match (self, enclosure) {
+            (Notation::Ipa, Enclosure::Quotation) => ("“", "”"),
+            (Notation::Ipa, Enclosure::Parenthetical) => ("{", "}"),
+            (Notation::Phonetic(alphabet, _), enclosure) => alphabet.brackets(enclosure),
+        }
+    }
+}
+
+// a transcription under a set of sandhi rules, `Ipa` uses the standard ones
+pub trait Transcribe {
+    fn transcribe_in(&self, sandhi: &Sandhi, notation: Notation) -> String;
+
+    fn transcribe(&self, sandhi: &Sandhi) -> String {
+        self.transcribe_in(sandhi, Notation::Ipa)
+    }
+}
+
+fn join(notation: Notation, word: &Word, phones: &[Phones]) -> String {
+    let mut buffer = String::new();
+
+    let mut first = true;
+    for (syllable, phones) in word.iter().zip(phones) {
+        if first {
+            first = false;
+        } else {
+            buffer.push('.')
+        }
+
+        buffer.push_str(&notation.phones(syllable, phones))
+    }
+
+    buffer
+}
+
+impl Transcribe for Syllable {
+    fn transcribe_in(&self, _: &Sandhi, notation: Notation) -> String {
+        notation.phones(self, &Phones::from(self))
+    }
+}
+
+impl Transcribe for Word {
+    fn transcribe_in(&self, sandhi: &Sandhi, notation: Notation) -> String {
+        join(notation, self, &sandhi.word_phones(self))
+    }
+}
+
+impl Transcribe for Phrase {
+    fn transcribe_in(&self, sandhi: &Sandhi, notation: Notation) -> String {
+        let mut buffer = String::new();
+
+        let mut first = true;
+        for (word, phones) in self.iter().zip(sandhi.phrase_phones(self)) {
+            if first {
+                first = false;
+            } else {
+                buffer.push(' ')
+            }
+
+            buffer.push_str(&join(notation, word, &phones))
+        }
+
+        buffer
+    }
+}
+
+// phrases are separated by commas and list items by minor group breaks. a question ends in a global
+// rise and an exclamation in a global fall
+impl Transcribe for Sentence {
+    fn transcribe_in(&self, sandhi: &Sandhi, notation: Notation) -> String {
+        let mut buffer = String::new();
+
+        let mut words = 0;
+        for (i, phrase) in self.iter().enumerate() {
+            if i > 0 {
+                match self.separator(i) {
+                    Separator::Phrase => buffer.push_str(", "),
+                    Separator::List => buffer.push_str(" | "),
+                }
+            }
+
+            for (j, (word, phones)) in phrase.iter().zip(sandhi.phrase_phones(phrase)).enumerate() {
+                if j > 0 {
+                    buffer.push(' ');
+                }
+
+                for span in self.spans.iter().filter(|span| span.start == words) {
+                    buffer.push_str(notation.brackets(span.enclosure).0);
+                }
+
+                buffer.push_str(&join(notation, word, &phones));
+                words += 1;
+
+                // inner spans close first
+                for span in self.spans.iter().rev().filter(|span| span.end == words) {
+                    buffer.push_str(notation.brackets(span.enclosure).1);
+                }
+            }
+        }
+
+        buffer.push_str(notation.terminator(self.terminator));
+
+        buffer
+    }
+}
+
+impl Transcribe for Paragraph {
+    fn transcribe_in(&self, sandhi: &Sandhi, notation: Notation) -> String {
+        let mut buffer = String::new();
+
+        for sentence in self.iter() {
+            buffer.push_str(&sentence.transcribe_in(sandhi, notation));
+            buffer.push_str(". ");
+        }
+
+        buffer
+    }
+}
+
+impl Transcribe for Text {
+    fn transcribe_in(&self, sandhi: &Sandhi, notation: Notation) -> String {
+        let mut buffer = String::new();
+
+        for paragraph in self.iter() {
+            buffer.push_str(&paragraph.transcribe_in(sandhi, notation));
+            buffer.push('\n');
+        }
+
+        buffer
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SandhiError {
+    // 1-based
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for SandhiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for SandhiError {}
+
+impl FromStr for Sandhi {
+    type Err = SandhiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sandhi = Sandhi::none();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let rule = parse_rule(line).map_err(|message| SandhiError {
+                line: i + 1,
+                message,
+            })?;
+
+            sandhi.rules.push(rule);
+        }
+
+        Ok(sandhi)
+    }
+}
+
+fn parse_rule(line: &str) -> Result<SandhiRule, String> {
+    let (head, body) = line
+        .split_once(':')
+        .ok_or("expected ':' after the rule's name")?;
+
+    let (scope, name) = match head.split_whitespace().collect::<Vec<_>>()[..] {
+        [name] => (Scope::Word, name),
+        ["word", name] => (Scope::Word, name),
+        ["phrase", name] => (Scope::Phrase, name),
+        [scope, _] => return Err(format!("unknown scope '{scope}', expected word or phrase")),
+        _ => return Err("expected a name without spaces before ':'".to_owned()),
+    };
+
+    let (condition, changes) = body
+        .split_once("->")
+        .ok_or("expected '->' before the changes")?;
+    let (left, right) = condition
+        .split_once('+')
+        .ok_or("expected '+' between the left and right patterns")?;
+
+    Ok(SandhiRule {
+        name: name.to_owned(),
+        scope,
+        left: parse_pattern(left)?,
+        right: parse_pattern(right)?,
+        changes: changes
+            .split(',')
+            .map(parse_change)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn parse_pattern(s: &str) -> Result<Pattern, String> {
+    fn values<X>(values: &str, value: impl Fn(&str) -> Option<X>) -> Result<Vec<X>, String> {
+        values
+            .split('|')
+            .map(|v| value(v).ok_or_else(|| format!("unknown value '{v}'")))
+            .collect()
+    }
+
+    let mut pattern = Pattern::any();
+
+    for term in s.split_whitespace().filter(|term| *term != "_") {
+        let (key, v) = term
+            .split_once('=')
+            .ok_or_else(|| format!("expected a term like 'onset=p', found '{term}'"))?;
+
+        pattern = match key {
+            "onset" => pattern.onset(values(v, |v| find(C::all(), v))?),
+            "vowel" => pattern.vowel(values(v, |v| find(V::all(), v))?),
+            "tone" => pattern.tone(values(v, tone)?),
+            "coda" => pattern.coda(values(v, |v| match v {
+                "none" => Some(None),
+                v => find(H::all(), v).map(Some),
+            })?),
+            key => return Err(format!("unknown slot '{key}'")),
+        };
+    }
+
+    Ok(pattern)
+}
+
+// a phoneme by its notation
+pub(crate) fn find<X: Display>(all: impl Iterator<Item = X>, s: &str) -> Option<X> {
+    all.into_iter().find(|x| x.to_string() == s)
+}
+
+// a tone by name or by its mark
+pub(crate) fn tone(s: &str) -> Option<T> {
+    match s {
+        "high" => Some(T::High),
+        "low" => Some(T::Low),
+        "peaking" => Some(T::Peaking),
+        "nasal" => Some(T::Nasal),
+        s => {
+            let mut chars = s.chars();
+            chars
+                .next()
+                .filter(|_| chars.next().is_none())
+                .and_then(T::from_mark)
+        }
+    }
+}
+
+fn parse_change(s: &str) -> Result<Change, String> {
+    let (target, edit) = match s.split_once("+=") {
+        Some((target, to)) => (target, Edit::Append(to.trim().to_owned())),
+        None => match s.split_once('=') {
+            Some((target, to)) => (target, Edit::Replace(to.trim().to_owned())),
+            None => {
+                return Err(format!(
+                    "expected a change like 'left.coda = p̚', found '{s}'"
+                ))
+            }
+        },
+    };
+
+    let (side, slot) = target.trim().split_once('.').ok_or_else(|| {
+        format!(
+            "expected a slot like 'left.coda', found '{}'",
+            target.trim()
+        )
+    })?;
+
+    let side = match side {
+        "left" => Side::Left,
+        "right" => Side::Right,
+        side => return Err(format!("unknown side '{side}', expected left or right")),
+    };
+
+    let slot = match slot {
+        "onset" => Slot::Onset,
+        "vowel" => Slot::Vowel,
+        "coda" => Slot::Coda,
+        "tone" => Slot::Tone,
+        slot => return Err(format!("unknown slot '{slot}'")),
+    };
+
+    Ok(Change { side, slot, edit })
+}
@@ -0,0 +1,77 @@
+use std::fmt::{self, Display};
+
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Parse;
+
+// for `#[serde(with = "wa::serde::compact")]`, writes a node as its notation, like "zẽy" instead of
+// `{"onset":{"C":{"Blunt":"S"}},"vowel":"E","tone":"Nasal","coda":"Y"}`
+pub mod compact {
+    use std::fmt::Display;
+
+    use serde::{
+        de::{Deserialize, Error},
+        Deserializer, Serializer,
+    };
+    use serde_derive::Deserialize;
+
+    use crate::{Parse, ParseMode};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Display,
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    // notation is parsed strictly, the verbose form that the plain derives write is still read
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Parse + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged, bound = "T: Deserialize<'de>")]
+        enum Repr<T> {
+            Compact(String),
+            Verbose(T),
+        }
+
+        match Repr::<T>::deserialize(deserializer)? {
+            Repr::Compact(notation) => T::parse_with(&notation, ParseMode::Strict)
+                .map_err(|error| D::Error::custom(format_args!("{notation:?}: {error}"))),
+            Repr::Verbose(value) => Ok(value),
+        }
+    }
+}
+
+// a node that (de)serializes through `compact`, for places `with` can't reach like map keys and
+// collections
+#[derive(Copy, Clone, Debug, Default, Deref, DerefMut, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Compact<T>(pub T);
+
+impl<T: Display> Display for Compact<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> From<T> for Compact<T> {
+    fn from(value: T) -> Self {
+        Compact(value)
+    }
+}
+
+impl<T: Display> Serialize for Compact<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        compact::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Parse + Deserialize<'de>> Deserialize<'de> for Compact<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        compact::deserialize(deserializer).map(Compact)
+    }
+}
@@ -0,0 +1,581 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    features::{Class, Features, Phoneme},
+    sandhi::{find, tone, Slot},
+    Paragraph, Phrase, Sentence, Syllable, Text, Word, C, H, T, V,
+};
+
+// a history is a list of sound changes, one per line and applied in order:
+//
+//   name: target -> replacement / environment
+//
+// the target is a slot of a syllable, either with the phonemes it applies to like `onset=p|t|k`
+// and `coda=h|none`, or with a natural class in brackets like `onset[plosive -voiced]`. the
+// replacement is a single phoneme or tone, one for each target phoneme like `b|d|g`, or features
+// to change like `[+voiced -aspirated]`. a phoneme whose changed features aren't in the inventory
+// is left alone.
+//
+// the environment is optional, it's a list of conditions that all have to hold: `initial` and
+// `final` for the syllable's place in the word, terms like the target's for the syllable itself,
+// and the same terms after `prev.` or `next.` for its neighbours in the word. `!` negates a
+// condition. a change applies to every syllable of a word at once, so it never feeds itself
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Value {
+    Onset(C),
+    Vowel(V),
+    Tone(T),
+    Coda(Option<H>),
+}
+
+impl Value {
+    pub fn of(syllable: &Syllable, slot: Slot) -> Self {
+        match slot {
+            Slot::Onset => Value::Onset(syllable.onset),
+            Slot::Vowel => Value::Vowel(syllable.vowel),
+            Slot::Tone => Value::Tone(syllable.tone),
+            Slot::Coda => Value::Coda(syllable.coda),
+        }
+    }
+
+    pub fn slot(&self) -> Slot {
+        match self {
+            Value::Onset(_) => Slot::Onset,
+            Value::Vowel(_) => Slot::Vowel,
+            Value::Tone(_) => Slot::Tone,
+            Value::Coda(_) => Slot::Coda,
+        }
+    }
+
+    pub fn set(&self, syllable: &mut Syllable) {
+        match *self {
+            Value::Onset(c) => syllable.onset = c,
+            Value::Vowel(v) => syllable.vowel = v,
+            Value::Tone(t) => syllable.tone = t,
+            Value::Coda(h) => syllable.coda = h,
+        }
+    }
+
+    // tones and empty codas have no features
+    pub fn features(&self) -> Option<Features> {
+        match self {
+            Value::Onset(c) => Some(c.features()),
+            Value::Vowel(v) => Some(v.features()),
+            Value::Tone(_) | Value::Coda(None) => None,
+            Value::Coda(Some(h)) => Some(h.features()),
+        }
+    }
+
+    // the phoneme of the same slot with exactly these features
+    fn with_features(&self, features: &Features) -> Option<Self> {
+        fn matching<X: Phoneme>(
+            mut all: impl Iterator<Item = X>,
+            features: &Features,
+        ) -> Option<X> {
+            all.find(|x| x.features() == *features)
+        }
+
+        match self {
+            Value::Onset(_) => matching(C::all(), features).map(Value::Onset),
+            Value::Vowel(_) => matching(V::all(), features).map(Value::Vowel),
+            Value::Tone(_) => None,
+            Value::Coda(_) => matching(H::all(), features).map(|h| Value::Coda(Some(h))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Term {
+    // one of these values
+    Is(Vec<Value>),
+    // a phoneme in a slot that belongs to the class
+    In(Slot, Class),
+}
+
+impl Term {
+    pub fn slot(&self) -> Option<Slot> {
+        match self {
+            Term::Is(values) => values.first().map(Value::slot),
+            Term::In(slot, _) => Some(*slot),
+        }
+    }
+
+    pub fn matches(&self, syllable: &Syllable) -> bool {
+        match self {
+            Term::Is(values) => values
+                .iter()
+                .any(|value| Value::of(syllable, value.slot()) == *value),
+            Term::In(slot, class) => Value::of(syllable, *slot)
+                .features()
+                .is_some_and(|features| class.matches(&features)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Neighbour {
+    Previous,
+    Next,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Condition {
+    // the first syllable of the word
+    Initial,
+    // the last syllable of the word
+    Final,
+    Syllable(Term),
+    // false when there is no such syllable in the word
+    Neighbour(Neighbour, Term),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    fn holds(&self, word: &[Syllable], i: usize) -> bool {
+        match self {
+            Condition::Initial => i == 0,
+            Condition::Final => i + 1 == word.len(),
+            Condition::Syllable(term) => term.matches(&word[i]),
+            Condition::Neighbour(neighbour, term) => {
+                let j = match neighbour {
+                    Neighbour::Previous => i.checked_sub(1),
+                    Neighbour::Next => Some(i + 1),
+                };
+
+                j.and_then(|j| word.get(j))
+                    .is_some_and(|syllable| term.matches(syllable))
+            }
+            Condition::Not(condition) => !condition.holds(word, i),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Replacement {
+    // a single value replaces every target, otherwise each target value is replaced by the value
+    // in the same position
+    Values(Vec<Value>),
+    // the features the class sets are changed
+    Features(Class),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SoundChange {
+    pub name: String,
+    pub target: Term,
+    pub replacement: Replacement,
+    pub environment: Vec<Condition>,
+}
+
+impl SoundChange {
+    fn replace(&self, value: Value) -> Option<Value> {
+        match &self.replacement {
+            Replacement::Values(values) => match (&self.target, &values[..]) {
+                (_, [value]) => Some(*value),
+                (Term::Is(targets), values) => targets
+                    .iter()
+                    .position(|target| *target == value)
+                    .and_then(|i| values.get(i).copied()),
+                _ => None,
+            },
+            Replacement::Features(class) => value.with_features(&impose(class, value.features()?)),
+        }
+    }
+
+    // the word after this change
+    pub fn apply(&self, word: &Word) -> Word {
+        let Some(slot) = self.target.slot() else {
+            return word.clone();
+        };
+
+        let mut changed = word.clone();
+
+        for (i, syllable) in word.iter().enumerate() {
+            let applies = self.target.matches(syllable)
+                && self
+                    .environment
+                    .iter()
+                    .all(|condition| condition.holds(word, i));
+
+            if applies {
+                if let Some(value) = self.replace(Value::of(syllable, slot)) {
+                    value.set(&mut changed[i]);
+                }
+            }
+        }
+
+        changed
+    }
+}
+
+// every feature the class sets, in place of the phoneme's own
+fn impose(class: &Class, features: Features) -> Features {
+    Features {
+        place: class.place.or(features.place),
+        manner: class.manner.unwrap_or(features.manner),
+        voiced: class.voiced.unwrap_or(features.voiced),
+        aspirated: class.aspirated.unwrap_or(features.aspirated),
+        palatalized: class.palatalized.unwrap_or(features.palatalized),
+        height: class.height.or(features.height),
+        backness: class.backness.or(features.backness),
+        rounded: class.rounded.unwrap_or(features.rounded),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Step {
+    pub change: String,
+    pub word: Word,
+}
+
+// a word through a history, with a step for every change that did something
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Derivation {
+    pub input: Word,
+    pub steps: Vec<Step>,
+}
+
+impl Derivation {
+    pub fn output(&self) -> &Word {
+        self.steps.last().map_or(&self.input, |step| &step.word)
+    }
+}
+
+impl Display for Derivation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.input)?;
+
+        for step in &self.steps {
+            write!(f, "\n  > {} ({})", step.word, step.change)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct History {
+    pub changes: Vec<SoundChange>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn change(mut self, change: SoundChange) -> Self {
+        self.changes.push(change);
+        self
+    }
+
+    pub fn derive(&self, word: &Word) -> Derivation {
+        let mut steps: Vec<Step> = Vec::new();
+
+        for change in &self.changes {
+            let before = steps.last().map_or(word, |step| &step.word);
+            let after = change.apply(before);
+
+            if after != *before {
+                steps.push(Step {
+                    change: change.name.clone(),
+                    word: after,
+                });
+            }
+        }
+
+        Derivation {
+            input: word.clone(),
+            steps,
+        }
+    }
+
+    pub fn apply(&self, word: &Word) -> Word {
+        self.changes
+            .iter()
+            .fold(word.clone(), |word, change| change.apply(&word))
+    }
+}
+
+// the words of a node after a history, a lone syllable is a word of one syllable
+pub trait Develop {
+    fn develop(&self, history: &History) -> Self
+    where
+        Self: Sized;
+
+    // the derivation of every word, in order
+    fn derivations(&self, history: &History) -> Vec<Derivation>;
+}
+
+impl Develop for Syllable {
+    fn develop(&self, history: &History) -> Self {
+        history.apply(&Word(vec![*self]))[0]
+    }
+
+    fn derivations(&self, history: &History) -> Vec<Derivation> {
+        vec![history.derive(&Word(vec![*self]))]
+    }
+}
+
+impl Develop for Word {
+    fn develop(&self, history: &History) -> Self {
+        history.apply(self)
+    }
+
+    fn derivations(&self, history: &History) -> Vec<Derivation> {
+        vec![history.derive(self)]
+    }
+}
+
+macro_rules! impl_develop {
+    ($ty:ident) => {
+        impl Develop for $ty {
+            fn develop(&self, history: &History) -> Self {
+                $ty(self.iter().map(|child| child.develop(history)).collect())
+            }
+
+            fn derivations(&self, history: &History) -> Vec<Derivation> {
+                self.iter()
+                    .flat_map(|child| child.derivations(history))
+                    .collect()
+            }
+        }
+    };
+}
+
+impl_develop!(Phrase);
+impl_develop!(Paragraph);
+impl_develop!(Text);
+
+// keeps the punctuation, which doesn't change
+impl Develop for Sentence {
+    fn develop(&self, history: &History) -> Self {
+        Sentence {
+            phrases: self.iter().map(|phrase| phrase.develop(history)).collect(),
+            separators: self.separators.clone(),
+            terminator: self.terminator,
+            spans: self.spans.clone(),
+        }
+    }
+
+    fn derivations(&self, history: &History) -> Vec<Derivation> {
+        self.iter()
+            .flat_map(|phrase| phrase.derivations(history))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SoundChangeError {
+    // 1-based
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for SoundChangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for SoundChangeError {}
+
+impl FromStr for History {
+    type Err = SoundChangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut history = History::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let change = parse_change(line).map_err(|message| SoundChangeError {
+                line: i + 1,
+                message,
+            })?;
+
+            history.changes.push(change);
+        }
+
+        Ok(history)
+    }
+}
+
+fn parse_change(line: &str) -> Result<SoundChange, String> {
+    let (name, body) = line
+        .split_once(':')
+        .ok_or("expected ':' after the change's name")?;
+
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err("expected a name without spaces before ':'".to_owned());
+    }
+
+    let (rewrite, environment) = body.split_once('/').unwrap_or((body, ""));
+    let (target, replacement) = rewrite
+        .split_once("->")
+        .ok_or("expected '->' between the target and the replacement")?;
+
+    let target = parse_term(target.trim())?;
+    let slot = target.slot().ok_or("expected a target")?;
+
+    let replacement = parse_replacement(slot, replacement.trim())?;
+
+    match (&target, &replacement) {
+        (Term::Is(targets), Replacement::Values(values))
+            if values.len() != 1 && values.len() != targets.len() =>
+        {
+            let expected = match targets.len() {
+                1 => "1 replacement".to_owned(),
+                n => format!("1 or {n} replacements"),
+            };
+
+            return Err(format!("expected {expected}, found {}", values.len()));
+        }
+        (Term::In(..), Replacement::Values(values)) if values.len() != 1 => {
+            return Err("expected a single replacement for a natural class".to_owned())
+        }
+        _ => (),
+    }
+
+    Ok(SoundChange {
+        name: name.to_owned(),
+        target,
+        replacement,
+        environment: tokens(environment)
+            .into_iter()
+            .map(parse_condition)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+// splits on spaces outside of brackets
+fn tokens(s: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut depth = 0;
+    let mut start = None;
+
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ch if ch.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    tokens.push(&s[start..i]);
+                }
+
+                continue;
+            }
+            _ => (),
+        }
+
+        start.get_or_insert(i);
+    }
+
+    if let Some(start) = start {
+        tokens.push(&s[start..]);
+    }
+
+    tokens
+}
+
+fn parse_slot(s: &str) -> Result<Slot, String> {
+    match s {
+        "onset" => Ok(Slot::Onset),
+        "vowel" => Ok(Slot::Vowel),
+        "tone" => Ok(Slot::Tone),
+        "coda" => Ok(Slot::Coda),
+        slot => Err(format!("unknown slot '{slot}'")),
+    }
+}
+
+fn parse_class(s: &str) -> Result<Class, String> {
+    let features = s
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| format!("expected features in brackets, found '{s}'"))?;
+
+    features.parse().map_err(|error| format!("{error}"))
+}
+
+fn parse_values(slot: Slot, s: &str) -> Result<Vec<Value>, String> {
+    s.split('|')
+        .map(|v| {
+            let value = match slot {
+                Slot::Onset => find(C::all(), v).map(Value::Onset),
+                Slot::Vowel => find(V::all(), v).map(Value::Vowel),
+                Slot::Tone => tone(v).map(Value::Tone),
+                Slot::Coda => match v {
+                    "none" => Some(Value::Coda(None)),
+                    v => find(H::all(), v).map(|h| Value::Coda(Some(h))),
+                },
+            };
+
+            value.ok_or_else(|| format!("unknown value '{v}'"))
+        })
+        .collect()
+}
+
+fn parse_term(s: &str) -> Result<Term, String> {
+    if let Some((slot, values)) = s.split_once('=') {
+        return Ok(Term::Is(parse_values(parse_slot(slot)?, values)?));
+    }
+
+    let Some(bracket) = s.find('[') else {
+        return Err(format!(
+            "expected a term like 'onset=p' or 'onset[plosive]', found '{s}'"
+        ));
+    };
+
+    let slot = parse_slot(&s[..bracket])?;
+    if slot == Slot::Tone {
+        return Err("tones have no features".to_owned());
+    }
+
+    Ok(Term::In(slot, parse_class(&s[bracket..])?))
+}
+
+fn parse_replacement(slot: Slot, s: &str) -> Result<Replacement, String> {
+    if !s.starts_with('[') {
+        return parse_values(slot, s).map(Replacement::Values);
+    }
+
+    if slot == Slot::Tone {
+        return Err("tones have no features".to_owned());
+    }
+
+    let class = parse_class(s)?;
+    if class.major.is_some() {
+        return Err(
+            "a replacement can't change obstruent or sonorant alone, change the manner".to_owned(),
+        );
+    }
+
+    Ok(Replacement::Features(class))
+}
+
+fn parse_condition(s: &str) -> Result<Condition, String> {
+    if let Some(condition) = s.strip_prefix('!') {
+        return Ok(Condition::Not(Box::new(parse_condition(condition)?)));
+    }
+
+    match s {
+        "initial" => return Ok(Condition::Initial),
+        "final" => return Ok(Condition::Final),
+        _ => (),
+    }
+
+    if let Some(term) = s.strip_prefix("prev.") {
+        return Ok(Condition::Neighbour(Neighbour::Previous, parse_term(term)?));
+    }
+
+    if let Some(term) = s.strip_prefix("next.") {
+        return Ok(Condition::Neighbour(Neighbour::Next, parse_term(term)?));
+    }
+
+    parse_term(s).map(Condition::Syllable)
+}
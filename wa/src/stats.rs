@@ -0,0 +1,219 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use crate::{Paragraph, Phrase, Sentence, Syllable, Text, Word, C, H, T, V};
+
+// how often every part of a syllable, every syllable and every word length occurs in a corpus.
+// `{:.n}` only lists the n most frequent of each, JSON always has everything
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub word_count: usize,
+    pub syllable_count: usize,
+    pub onsets: BTreeMap<C, usize>,
+    pub vowels: BTreeMap<V, usize>,
+    pub tones: BTreeMap<T, usize>,
+    pub codas: BTreeMap<Option<H>, usize>,
+    pub syllables: BTreeMap<Syllable, usize>,
+    // in syllables
+    pub word_lengths: BTreeMap<usize, usize>,
+    // syllables next to each other in a word, not across words
+    pub bigrams: BTreeMap<(Syllable, Syllable), usize>,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, node: &impl Tally) {
+        node.tally(self);
+    }
+
+    pub fn add_word(&mut self, word: &Word) {
+        self.word_count += 1;
+        *self.word_lengths.entry(word.len()).or_default() += 1;
+
+        for syllable in word.iter() {
+            self.syllable_count += 1;
+
+            *self.onsets.entry(syllable.onset).or_default() += 1;
+            *self.vowels.entry(syllable.vowel).or_default() += 1;
+            *self.tones.entry(syllable.tone).or_default() += 1;
+            *self.codas.entry(syllable.coda).or_default() += 1;
+            *self.syllables.entry(*syllable).or_default() += 1;
+        }
+
+        for pair in word.windows(2) {
+            *self.bigrams.entry((pair[0], pair[1])).or_default() += 1;
+        }
+    }
+
+    pub fn bigram_count(&self) -> usize {
+        self.bigrams.values().sum()
+    }
+
+    // the share of the possible syllables that occur at least once
+    pub fn coverage(&self) -> f64 {
+        self.syllables.len() as f64 / Syllable::COUNT as f64
+    }
+}
+
+pub trait Tally {
+    fn tally(&self, stats: &mut Stats);
+
+    fn stats(&self) -> Stats {
+        let mut stats = Stats::new();
+        self.tally(&mut stats);
+
+        stats
+    }
+}
+
+impl Tally for Word {
+    fn tally(&self, stats: &mut Stats) {
+        stats.add_word(self);
+    }
+}
+
+macro_rules! impl_tally {
+    ($($ty:ty),*) => {
+        $(
+            impl Tally for $ty {
+                fn tally(&self, stats: &mut Stats) {
+                    for child in self.iter() {
+                        child.tally(stats);
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_tally!(Phrase, Sentence, Paragraph, Text);
+
+fn coda_label(coda: &Option<H>) -> String {
+    coda.map_or_else(|| "none".to_owned(), |coda| coda.to_string())
+}
+
+fn tone_label(tone: &T) -> String {
+    format!("{tone:?}").to_lowercase()
+}
+
+fn bigram_label((a, b): &(Syllable, Syllable)) -> String {
+    format!("{a}-{b}")
+}
+
+fn percent(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        100.0 * count as f64 / total as f64
+    }
+}
+
+// a heading and the rows under it, most frequent first
+fn write_counts<K: Ord>(
+    f: &mut fmt::Formatter<'_>,
+    heading: &str,
+    counts: &BTreeMap<K, usize>,
+    total: usize,
+    label: impl Fn(&K) -> String,
+) -> fmt::Result {
+    let mut rows: Vec<_> = counts.iter().collect();
+    rows.sort_by(|(a, m), (b, n)| n.cmp(m).then(a.cmp(b)));
+
+    writeln!(f)?;
+    writeln!(f, "{heading}")?;
+
+    let shown = f.precision().unwrap_or(rows.len()).min(rows.len());
+    for (key, &count) in &rows[..shown] {
+        writeln!(
+            f,
+            "  {:<12} {count:>8} {:>6.2}%",
+            label(key),
+            percent(count, total)
+        )?;
+    }
+
+    if shown < rows.len() {
+        writeln!(f, "  … {} more", rows.len() - shown)?;
+    }
+
+    Ok(())
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "words      {:>8}", self.word_count)?;
+        writeln!(f, "syllables  {:>8}", self.syllable_count)?;
+        writeln!(
+            f,
+            "used       {:>8} of {} ({:.2}%)",
+            self.syllables.len(),
+            Syllable::COUNT,
+            100.0 * self.coverage()
+        )?;
+
+        let syllables = self.syllable_count;
+        write_counts(f, "onsets", &self.onsets, syllables, C::to_string)?;
+        write_counts(f, "vowels", &self.vowels, syllables, V::to_string)?;
+        write_counts(f, "tones", &self.tones, syllables, tone_label)?;
+        write_counts(f, "codas", &self.codas, syllables, coda_label)?;
+        write_counts(
+            f,
+            "syllables",
+            &self.syllables,
+            syllables,
+            Syllable::to_string,
+        )?;
+        write_counts(
+            f,
+            "word lengths",
+            &self.word_lengths,
+            self.word_count,
+            usize::to_string,
+        )?;
+        write_counts(
+            f,
+            "bigrams",
+            &self.bigrams,
+            self.bigram_count(),
+            bigram_label,
+        )
+    }
+}
+
+// a map written with its keys as labels, in the map's order
+struct Labelled<'a, K>(&'a BTreeMap<K, usize>, fn(&K) -> String);
+
+impl<K> Serialize for Labelled<'_, K> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(key, count)| ((self.1)(key), count)))
+    }
+}
+
+impl Serialize for Stats {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut stats = serializer.serialize_struct("Stats", 10)?;
+
+        stats.serialize_field("word_count", &self.word_count)?;
+        stats.serialize_field("syllable_count", &self.syllable_count)?;
+        stats.serialize_field("coverage", &self.coverage())?;
+        stats.serialize_field("onsets", &Labelled(&self.onsets, C::to_string))?;
+        stats.serialize_field("vowels", &Labelled(&self.vowels, V::to_string))?;
+        stats.serialize_field("tones", &Labelled(&self.tones, tone_label))?;
+        stats.serialize_field("codas", &Labelled(&self.codas, coda_label))?;
+        stats.serialize_field("syllables", &Labelled(&self.syllables, Syllable::to_string))?;
+        stats.serialize_field(
+            "word_lengths",
+            &Labelled(&self.word_lengths, usize::to_string),
+        )?;
+        stats.serialize_field("bigrams", &Labelled(&self.bigrams, bigram_label))?;
+
+        stats.end()
+    }
+}
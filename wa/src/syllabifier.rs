@@ -0,0 +1,102 @@
+use std::ops::Range;
+
+use unicode_normalization::char::is_combining_mark;
+
+use crate::{Parse, ParseError, ParseMode, Syllable, Word, T};
+
+// the longest a syllable can be written, in chars: a two letter onset, a vowel, a tone mark, a coda
+// and a stray combining mark for the strict parser to complain about
+const MAX_SYLLABLE: usize = 6;
+
+// syllables with their byte ranges in the segmented input
+pub(crate) type Segmentation = Vec<(Syllable, Range<usize>)>;
+
+// every way of splitting `s` into syllables. the ones with the longest first syllable come first,
+// which means codas are preferred over onsets
+pub(crate) fn segmentations(s: &str) -> Vec<Segmentation> {
+    let boundaries: Vec<usize> = s
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(s.len()))
+        .collect();
+
+    // from[i] holds the segmentations of everything from the i-th boundary on
+    let mut from: Vec<Vec<Segmentation>> = vec![Vec::new(); boundaries.len()];
+    from[boundaries.len() - 1].push(Vec::new());
+
+    for i in (0..boundaries.len() - 1).rev() {
+        let last = (i + MAX_SYLLABLE).min(boundaries.len() - 1);
+
+        for j in (i + 1..=last).rev() {
+            let range = boundaries[i]..boundaries[j];
+
+            let Ok(syllable) = Syllable::parse_with(&s[range.clone()], ParseMode::Strict) else {
+                continue;
+            };
+
+            let rests = from[j].clone();
+            for mut rest in rests {
+                rest.insert(0, (syllable, range.clone()));
+                from[i].push(rest);
+            }
+        }
+    }
+
+    from.swap_remove(0)
+}
+
+// splits a run of letters and tone marks written without hyphens, like `xúyzẽ`, into syllables. the
+// hyphens can be left out because a coda can't be followed by a vowel, so every syllable's end is
+// found by the next onset, but all segmentations are returned in case there is more than one
+pub fn syllabify(s: &str) -> Result<Vec<Word>, ParseError> {
+    let segmentations = segmentations(s);
+
+    if !s.is_empty() && !segmentations.is_empty() {
+        return Ok(segmentations
+            .into_iter()
+            .map(|segmentation| {
+                Word(
+                    segmentation
+                        .into_iter()
+                        .map(|(syllable, _)| syllable)
+                        .collect(),
+                )
+            })
+            .collect());
+    }
+
+    // report the error from the furthest a segmentation gets. `reached[i]` is where a syllable
+    // ending at byte i starts
+    let mut reached = vec![None; s.len() + 1];
+    reached[0] = Some(0);
+
+    let mut furthest = 0;
+    for start in 0..s.len() {
+        if reached[start].is_none() {
+            continue;
+        }
+
+        furthest = start;
+
+        for (end, _) in s[start..].char_indices().skip(1).take(MAX_SYLLABLE) {
+            if Syllable::parse_with(&s[start..start + end], ParseMode::Strict).is_ok() {
+                reached[start + end] = Some(start);
+            }
+        }
+    }
+
+    // a mark can't start a syllable, it's left over from the one before, which the strict parser
+    // reports as a stray or doubled mark
+    let start = match s[furthest..].chars().next() {
+        Some(ch) if is_combining_mark(ch) || T::from_mark(ch).is_some() => {
+            reached[furthest].unwrap_or(furthest)
+        }
+        _ => furthest,
+    };
+
+    match Syllable::parse_with(&s[start..], ParseMode::Strict) {
+        Err(error) => Err(error.shift(start)),
+        // only the whole of `s` is a syllable, which `segmentations` would have found
+        Ok(_) => unreachable!("a syllable reaching the end of the input is a segmentation"),
+    }
+}
@@ -0,0 +1,635 @@
+use std::{
+    f32::consts::{PI, TAU},
+    fmt::Display,
+    io::{self, Write},
+};
+
+use serde_derive::*;
+
+use crate::{CStem, Paragraph, Phrase, PureC, Sentence, Syllable, Text, Word, C, H, V};
+
+// every length is in seconds, every pitch in Hz
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Voice {
+    pub sample_rate: u32,
+    // the bottom and top of a tone contour
+    pub low_pitch: f32,
+    pub high_pitch: f32,
+    // above 1 speaks faster
+    pub rate: f32,
+    // the loudest sample, from 0 to 1
+    pub volume: f32,
+
+    pub word_gap: f32,
+    pub phrase_gap: f32,
+    pub sentence_gap: f32,
+    pub paragraph_gap: f32,
+}
+
+impl Default for Voice {
+    fn default() -> Self {
+        Self {
+            sample_rate: 22050,
+            low_pitch: 95.,
+            high_pitch: 165.,
+            rate: 1.,
+            volume: 0.8,
+
+            word_gap: 0.06,
+            phrase_gap: 0.2,
+            sentence_gap: 0.4,
+            paragraph_gap: 0.7,
+        }
+    }
+}
+
+impl Voice {
+    // a sample rate or rate of 0 would never finish rendering, and an infinite gap would never end
+    pub fn validate(&self) -> Result<(), VoiceError> {
+        let error = |field, expected| Err(VoiceError { field, expected });
+
+        if self.sample_rate == 0 {
+            return error("sample_rate", "above 0");
+        }
+        if !(self.rate.is_finite() && self.rate > 0.) {
+            return error("rate", "a number above 0");
+        }
+        if !(self.low_pitch.is_finite() && self.low_pitch > 0.) {
+            return error("low_pitch", "a number above 0");
+        }
+        if !(self.high_pitch.is_finite() && self.high_pitch >= self.low_pitch) {
+            return error("high_pitch", "a number no lower than low_pitch");
+        }
+        if !(0. ..=1.).contains(&self.volume) {
+            return error("volume", "from 0 to 1");
+        }
+
+        let gaps = [
+            ("word_gap", self.word_gap),
+            ("phrase_gap", self.phrase_gap),
+            ("sentence_gap", self.sentence_gap),
+            ("paragraph_gap", self.paragraph_gap),
+        ];
+
+        for (field, gap) in gaps {
+            if !(gap.is_finite() && gap >= 0.) {
+                return error(field, "a number from 0 up");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VoiceError {
+    pub field: &'static str,
+    pub expected: &'static str,
+}
+
+impl Display for VoiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the voice's {} must be {}", self.field, self.expected)
+    }
+}
+
+impl std::error::Error for VoiceError {}
+
+pub trait Speak {
+    fn speak_into(&self, synthesizer: &mut Synthesizer);
+
+    fn speak(&self, voice: &Voice) -> Result<Vec<f32>, VoiceError> {
+        let mut synthesizer = Synthesizer::new(voice.clone())?;
+        self.speak_into(&mut synthesizer);
+        Ok(synthesizer.finish())
+    }
+}
+
+impl Speak for Syllable {
+    fn speak_into(&self, synthesizer: &mut Synthesizer) {
+        synthesizer.syllable(self);
+    }
+}
+
+impl Speak for Word {
+    fn speak_into(&self, synthesizer: &mut Synthesizer) {
+        for syllable in self.iter() {
+            synthesizer.syllable(syllable);
+        }
+    }
+}
+
+impl Speak for Phrase {
+    fn speak_into(&self, synthesizer: &mut Synthesizer) {
+        let mut first = true;
+
+        for word in self.iter() {
+            if !first {
+                synthesizer.pause(synthesizer.voice.word_gap);
+            }
+            first = false;
+
+            word.speak_into(synthesizer);
+        }
+    }
+}
+
+impl Speak for Sentence {
+    fn speak_into(&self, synthesizer: &mut Synthesizer) {
+        let mut first = true;
+
+        for phrase in self.iter() {
+            if !first {
+                synthesizer.pause(synthesizer.voice.phrase_gap);
+            }
+            first = false;
+
+            phrase.speak_into(synthesizer);
+        }
+
+        synthesizer.pause(synthesizer.voice.sentence_gap);
+    }
+}
+
+impl Speak for Paragraph {
+    fn speak_into(&self, synthesizer: &mut Synthesizer) {
+        for sentence in self.iter() {
+            sentence.speak_into(synthesizer);
+        }
+    }
+}
+
+impl Speak for Text {
+    fn speak_into(&self, synthesizer: &mut Synthesizer) {
+        let mut first = true;
+
+        for paragraph in self.iter() {
+            if !first {
+                synthesizer.pause(synthesizer.voice.paragraph_gap);
+            }
+            first = false;
+
+            paragraph.speak_into(synthesizer);
+        }
+    }
+}
+
+// a source-filter synthesizer: a glottal pulse train and a noise source are shaped by three
+// formant resonators, with a separate fricative band and a nasal murmur
+pub struct Synthesizer {
+    voice: Voice,
+    samples: Vec<f32>,
+
+    // position within the current glottal period, from 0 to 1
+    phase: f32,
+    previous_pulse: f32,
+    noise: u32,
+    formants: [Resonator; 3],
+    frication: Resonator,
+    nasal: Resonator,
+}
+
+impl Synthesizer {
+    pub fn new(voice: Voice) -> Result<Self, VoiceError> {
+        voice.validate()?;
+
+        Ok(Self {
+            voice,
+            samples: Vec::new(),
+
+            phase: 0.,
+            previous_pulse: 0.,
+            noise: 0x9E37_79B9,
+            formants: Default::default(),
+            frication: Resonator::default(),
+            nasal: Resonator::default(),
+        })
+    }
+
+    pub fn voice(&self) -> &Voice {
+        &self.voice
+    }
+
+    pub fn pause(&mut self, seconds: f32) {
+        self.render(&[Segment::hold(seconds, Frame::SILENCE)], 0., 1., |_| 0.);
+    }
+
+    pub fn syllable(&mut self, syllable: &Syllable) {
+        let (segments, vowel_start) = segments(syllable);
+
+        let low = self.voice.low_pitch;
+        let high = self.voice.high_pitch;
+        let tone = syllable.tone;
+
+        // the contour runs over the voiced rhyme, the onset keeps its starting pitch
+        self.render(&segments, vowel_start, 1., |time| {
+            low + (high - low) * tone.pitch_at(time)
+        });
+    }
+
+    // the samples so far, scaled so the loudest is at the voice's volume
+    pub fn finish(self) -> Vec<f32> {
+        let peak = self
+            .samples
+            .iter()
+            .fold(0f32, |peak, sample| peak.max(sample.abs()));
+
+        if peak == 0. {
+            return self.samples;
+        }
+
+        let gain = self.voice.volume / peak;
+
+        self.samples.into_iter().map(|s| s * gain).collect()
+    }
+
+    pub fn write_wav(self, writer: impl Write) -> io::Result<()> {
+        let sample_rate = self.voice.sample_rate;
+        write_wav(writer, &self.finish(), sample_rate)
+    }
+
+    // `pitch` gets the time relative to the span from `from` to `to` of the total length
+    fn render(&mut self, segments: &[Segment], from: f32, to: f32, pitch: impl Fn(f32) -> f32) {
+        let sample_rate = self.voice.sample_rate as f32;
+        let total: f32 = segments.iter().map(|segment| segment.length).sum();
+        let total = total / self.voice.rate;
+
+        let mut elapsed = 0.;
+
+        for segment in segments {
+            let length = segment.length / self.voice.rate;
+            let count = (length * sample_rate).round() as usize;
+
+            for i in 0..count {
+                let t = i as f32 / count as f32;
+                let frame = segment.start.lerp(&segment.end, t);
+
+                let time = elapsed + i as f32 / sample_rate;
+                let relative = if total > 0. {
+                    ((time / total - from) / (to - from)).clamp(0., 1.)
+                } else {
+                    0.
+                };
+
+                let sample = self.sample(&frame, pitch(relative), time);
+                self.samples.push(sample);
+            }
+
+            elapsed += length;
+        }
+    }
+
+    fn sample(&mut self, frame: &Frame, pitch: f32, time: f32) -> f32 {
+        let sample_rate = self.voice.sample_rate as f32;
+
+        // a Rosenberg pulse, differentiated for the radiation at the lips
+        self.phase = (self.phase + pitch / sample_rate).fract();
+        let pulse = match self.phase {
+            p if p < 0.4 => 0.5 * (1. - (PI * p / 0.4).cos()),
+            p if p < 0.6 => (PI / 2. * (p - 0.4) / 0.2).cos(),
+            _ => 0.,
+        };
+        let glottal = pulse - self.previous_pulse;
+        self.previous_pulse = pulse;
+
+        let trill = 1. - frame.trill * 0.5 * (1. + (TAU * 28. * time).cos());
+        let voicing = glottal * frame.voicing * trill * 8.;
+
+        let noise = self.noise();
+
+        let mut oral = voicing * (1. - 0.4 * frame.nasal) + noise * frame.aspiration * 0.3;
+        for (i, resonator) in self.formants.iter_mut().enumerate() {
+            let bandwidth = BANDWIDTHS[i] * if i == 0 { 1. + 2. * frame.nasal } else { 1. };
+            oral = resonator.process(oral, frame.formants[i], bandwidth, sample_rate);
+        }
+
+        let nasal = self.nasal.process(voicing, 250., 100., sample_rate) * frame.nasal;
+
+        let frequency = frame.frication_frequency;
+        let frication = self
+            .frication
+            .process(noise, frequency, frequency * 0.4, sample_rate)
+            * frame.frication
+            * 0.4;
+
+        oral + nasal + frication
+    }
+
+    // xorshift, so the same text always gives the same samples
+    fn noise(&mut self) -> f32 {
+        self.noise ^= self.noise << 13;
+        self.noise ^= self.noise >> 17;
+        self.noise ^= self.noise << 5;
+
+        self.noise as f32 / u32::MAX as f32 * 2. - 1.
+    }
+}
+
+const BANDWIDTHS: [f32; 3] = [70., 100., 160.];
+
+// a two-pole resonator with unity gain at its centre frequency
+#[derive(Copy, Clone, Debug, Default)]
+struct Resonator {
+    y1: f32,
+    y2: f32,
+}
+
+impl Resonator {
+    fn process(&mut self, x: f32, frequency: f32, bandwidth: f32, sample_rate: f32) -> f32 {
+        let r = (-PI * bandwidth / sample_rate).exp();
+        let theta = TAU * frequency.min(sample_rate * 0.45) / sample_rate;
+
+        let b = 2. * r * theta.cos();
+        let c = -r * r;
+        let a = (1. - r) * (1. - 2. * r * (2. * theta).cos() + r * r).sqrt();
+
+        let y = a * x + b * self.y1 + c * self.y2;
+        self.y2 = self.y1;
+        self.y1 = y;
+
+        y
+    }
+}
+
+// the synthesizer's controls at one instant, every amount is from 0 to 1
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct Frame {
+    formants: [f32; 3],
+    voicing: f32,
+    // noise through the formants, like [h]
+    aspiration: f32,
+    // noise through its own band, like [s] and [x]
+    frication: f32,
+    frication_frequency: f32,
+    nasal: f32,
+    trill: f32,
+}
+
+impl Frame {
+    const SILENCE: Frame = Frame {
+        formants: [250., 900., 2400.],
+        voicing: 0.,
+        aspiration: 0.,
+        frication: 0.,
+        frication_frequency: 1000.,
+        nasal: 0.,
+        trill: 0.,
+    };
+
+    fn voiced(formants: [f32; 3]) -> Frame {
+        Frame {
+            formants,
+            voicing: 1.,
+            ..Frame::SILENCE
+        }
+    }
+
+    fn lerp(&self, other: &Frame, t: f32) -> Frame {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+
+        Frame {
+            formants: [
+                mix(self.formants[0], other.formants[0]),
+                mix(self.formants[1], other.formants[1]),
+                mix(self.formants[2], other.formants[2]),
+            ],
+            voicing: mix(self.voicing, other.voicing),
+            aspiration: mix(self.aspiration, other.aspiration),
+            frication: mix(self.frication, other.frication),
+            frication_frequency: mix(self.frication_frequency, other.frication_frequency),
+            nasal: mix(self.nasal, other.nasal),
+            trill: mix(self.trill, other.trill),
+        }
+    }
+}
+
+fn vowel_formants(vowel: V) -> [f32; 3] {
+    match vowel {
+        V::A => [780., 1250., 2500.],
+        V::E => [460., 1850., 2550.],
+        V::I => [290., 2250., 3000.],
+        V::O => [460., 850., 2450.],
+        V::U => [310., 800., 2350.],
+    }
+}
+
+// the frequency of a plosive's burst, by place
+fn burst_frequency(stem: CStem) -> f32 {
+    match stem {
+        CStem::P => 800.,
+        CStem::T => 4000.,
+        CStem::K => 1800.,
+        CStem::S => 5500.,
+        CStem::R => 1500.,
+    }
+}
+
+// a frame is faded into the next over a segment
+#[derive(Copy, Clone, Debug)]
+struct Segment {
+    length: f32,
+    start: Frame,
+    end: Frame,
+}
+
+impl Segment {
+    fn new(length: f32, start: Frame, end: Frame) -> Self {
+        Self { length, start, end }
+    }
+
+    fn hold(length: f32, frame: Frame) -> Self {
+        Self::new(length, frame, frame)
+    }
+}
+
+// the syllable's segments, and where its vowel starts as a fraction of its length
+fn segments(syllable: &Syllable) -> (Vec<Segment>, f32) {
+    let mut vowel = Frame::voiced(vowel_formants(syllable.vowel));
+    if syllable.tone.is_nasal() {
+        vowel.nasal = 1.;
+    }
+
+    let palatal = Frame::voiced(vowel_formants(V::I));
+    let labial = Frame::voiced(vowel_formants(V::U));
+
+    let mut segments = Vec::new();
+
+    match syllable.onset {
+        C::C(c) => {
+            let stem = c.stem();
+            let voicing = if let PureC::Blunt(_) = c { 0.25 } else { 0. };
+
+            match stem {
+                CStem::P | CStem::T | CStem::K => {
+                    let closure = Frame {
+                        voicing,
+                        ..Frame::SILENCE
+                    };
+                    let burst = Frame {
+                        frication: 1.,
+                        frication_frequency: burst_frequency(stem),
+                        ..closure
+                    };
+
+                    segments.push(Segment::hold(0.05, closure));
+                    segments.push(Segment::hold(0.012, burst));
+                }
+                CStem::S => {
+                    let frequency = if let PureC::Sharp(_) = c {
+                        3500.
+                    } else {
+                        5500.
+                    };
+                    let fricative = Frame {
+                        voicing: voicing * 2.,
+                        frication: 1.,
+                        frication_frequency: frequency,
+                        ..Frame::SILENCE
+                    };
+
+                    segments.push(Segment::hold(0.1, fricative));
+                }
+                CStem::R => {
+                    let liquid = match c {
+                        PureC::Strong(_) => Frame {
+                            trill: 1.,
+                            ..Frame::voiced([500., 1300., 1700.])
+                        },
+                        PureC::Blunt(_) => Frame::voiced([400., 1100., 1500.]),
+                        PureC::Sharp(_) => Frame::voiced([350., 1100., 2700.]),
+                    };
+
+                    segments.push(Segment::hold(0.06, liquid));
+                    segments.push(Segment::new(0.04, liquid, vowel));
+                }
+            }
+
+            match c {
+                // aspiration
+                PureC::Strong(CStem::P | CStem::T | CStem::K) => {
+                    let aspirated = Frame {
+                        voicing: 0.,
+                        aspiration: 1.,
+                        nasal: 0.,
+                        ..vowel
+                    };
+
+                    segments.push(Segment::new(0.05, aspirated, vowel));
+                }
+                // palatalisation, the liquid l is already sharp enough
+                PureC::Sharp(stem) if stem != CStem::R => {
+                    segments.push(Segment::new(0.045, palatal, vowel));
+                }
+                _ => {}
+            }
+        }
+        C::H(h) => match h {
+            H::W | H::Y => {
+                let glide = if h == H::W { labial } else { palatal };
+
+                segments.push(Segment::hold(0.04, glide));
+                segments.push(Segment::new(0.05, glide, vowel));
+            }
+            H::X => {
+                let fricative = Frame {
+                    frication: 1.,
+                    frication_frequency: 1500.,
+                    ..Frame::SILENCE
+                };
+
+                segments.push(Segment::hold(0.08, fricative));
+            }
+            H::H => {
+                let breath = Frame {
+                    voicing: 0.,
+                    aspiration: 1.,
+                    nasal: 0.,
+                    ..vowel
+                };
+
+                segments.push(Segment::new(0.06, breath, vowel));
+            }
+        },
+    }
+
+    let onset: f32 = segments.iter().map(|segment| segment.length).sum();
+
+    segments.push(Segment::hold(0.2, vowel));
+
+    let fade = Frame {
+        voicing: 0.,
+        ..vowel
+    };
+
+    match syllable.coda {
+        Some(h @ (H::W | H::Y)) => {
+            let glide = Frame {
+                nasal: vowel.nasal,
+                ..if h == H::W { labial } else { palatal }
+            };
+
+            segments.push(Segment::new(0.08, vowel, glide));
+            segments.push(Segment::new(
+                0.02,
+                glide,
+                Frame {
+                    voicing: 0.,
+                    ..glide
+                },
+            ));
+        }
+        Some(H::X) => {
+            let fricative = Frame {
+                frication: 0.8,
+                frication_frequency: 1500.,
+                ..fade
+            };
+
+            segments.push(Segment::new(0.03, vowel, fricative));
+            segments.push(Segment::new(0.05, fricative, Frame::SILENCE));
+        }
+        Some(H::H) => {
+            let breath = Frame {
+                aspiration: 0.8,
+                ..fade
+            };
+
+            segments.push(Segment::new(0.02, vowel, breath));
+            segments.push(Segment::new(0.05, breath, fade));
+        }
+        None => segments.push(Segment::new(0.03, vowel, fade)),
+    }
+
+    let total: f32 = segments.iter().map(|segment| segment.length).sum();
+
+    (segments, onset / total)
+}
+
+// 16 bit mono PCM, samples are clipped to -1..1
+pub fn write_wav(mut writer: impl Write, samples: &[f32], sample_rate: u32) -> io::Result<()> {
+    let data_length = samples.len() as u32 * 2;
+
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(36 + data_length).to_le_bytes())?;
+    writer.write_all(b"WAVE")?;
+
+    writer.write_all(b"fmt ")?;
+    writer.write_all(&16u32.to_le_bytes())?;
+    writer.write_all(&1u16.to_le_bytes())?; // PCM
+    writer.write_all(&1u16.to_le_bytes())?; // channels
+    writer.write_all(&sample_rate.to_le_bytes())?;
+    writer.write_all(&(sample_rate * 2).to_le_bytes())?; // bytes per second
+    writer.write_all(&2u16.to_le_bytes())?; // bytes per sample
+    writer.write_all(&16u16.to_le_bytes())?; // bits per sample
+
+    writer.write_all(b"data")?;
+    writer.write_all(&data_length.to_le_bytes())?;
+
+    for sample in samples {
+        let sample = (sample.clamp(-1., 1.) * i16::MAX as f32) as i16;
+        writer.write_all(&sample.to_le_bytes())?;
+    }
+
+    writer.flush()
+}
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use wa::{
+    collation::{Collate, Order},
+    Syllable, Word, C, H, T, V,
+};
+
+const ORDERS: [Order; 2] = [Order::Native, Order::Romanized];
+
+fn words(words: &[&str]) -> Vec<Word> {
+    words.iter().map(|word| wa::word(word)).collect()
+}
+
+fn sorted(order: Order, unsorted: &[&str]) -> Vec<Word> {
+    let mut words = words(unsorted);
+    order.sort(&mut words);
+
+    words
+}
+
+// a value missing from an order would make ranking it panic
+#[test]
+fn every_order_ranks_every_value_once() {
+    fn check<X: Eq + std::hash::Hash + std::fmt::Debug>(list: &[X], all: impl Iterator<Item = X>) {
+        let all: HashSet<X> = all.collect();
+
+        assert_eq!(list.len(), all.len());
+        assert_eq!(list.iter().collect::<HashSet<_>>(), all.iter().collect());
+    }
+
+    for order in ORDERS {
+        check(order.onsets(), C::all());
+        check(order.vowels(), V::all());
+        check(order.tones(), T::all());
+        check(
+            order.codas(),
+            std::iter::once(None).chain(H::all().map(Some)),
+        );
+    }
+
+    for syllable in Syllable::all() {
+        for order in ORDERS {
+            syllable.sort_key(order);
+        }
+    }
+}
+
+#[test]
+fn words_sort_in_either_order() {
+    let unsorted = ["ha,", "ka,x", "pa,", "za,", "ka,", "ya,", "ka,-xu,", "ba,"];
+
+    assert_eq!(
+        sorted(Order::Native, &unsorted),
+        words(&["pa,", "ba,", "ka,", "ka,-xu,", "ka,x", "za,", "ya,", "ha,"])
+    );
+    assert_eq!(
+        sorted(Order::Romanized, &unsorted),
+        words(&["ba,", "ha,", "ka,", "ka,-xu,", "ka,x", "pa,", "ya,", "za,"])
+    );
+}
+
+#[test]
+fn tones_only_break_ties_between_letters() {
+    let unsorted = ["ka,-ta`", "ka`-pa,", "ka`-ta,", "ka,-ta,"];
+
+    for order in ORDERS {
+        assert_eq!(
+            sorted(order, &unsorted),
+            words(&["ka`-pa,", "ka,-ta,", "ka,-ta`", "ka`-ta,"])
+        );
+    }
+
+    let a = wa::word("ka,-ta`").sort_key(Order::Native);
+    let b = wa::word("ka`-ta,").sort_key(Order::Native);
+    assert_eq!(a.primary(), b.primary());
+    assert_ne!(a, b);
+}
+
+#[test]
+fn keys_order_by_their_primary_part_first() {
+    let words = words(&[
+        "ka,", "ka`", "ka,x", "pa,-ka`", "pa^-ka,", "sye~", "ha,-ha,",
+    ]);
+
+    for order in ORDERS {
+        for a in &words {
+            for b in &words {
+                let ordering = order.compare(a, b);
+                let (a, b) = (a.sort_key(order), b.sort_key(order));
+
+                assert_eq!(ordering, a.cmp(&b));
+                if a.primary() != b.primary() {
+                    assert_eq!(ordering, a.primary().cmp(b.primary()));
+                }
+            }
+        }
+    }
+}
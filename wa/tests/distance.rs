@@ -0,0 +1,108 @@
+use wa::{
+    distance::{Distance, Edit, Weights},
+    CStem, PureC, Word, C, T,
+};
+
+#[test]
+fn voicing_is_closer_than_place() {
+    let t = C::C(PureC::Strong(CStem::T));
+    let d = C::C(PureC::Blunt(CStem::T));
+    let k = C::C(PureC::Strong(CStem::K));
+
+    assert!(t.distance(&d) < t.distance(&k));
+    assert_eq!(t.distance(&t), 0.);
+}
+
+#[test]
+fn a_tone_change_costs_the_tone_weight() {
+    let weights = Weights {
+        tone: 0.7,
+        ..Weights::default()
+    };
+
+    assert_eq!(T::High.distance_with(&T::Low, &weights), 0.7);
+    assert_eq!(
+        wa::syllable("ka,").distance_with(&wa::syllable("ka~"), &weights),
+        0.7
+    );
+    assert_eq!(T::High.distance_with(&T::High, &weights), 0.);
+}
+
+#[test]
+fn alignments_pair_up_syllables() {
+    let weights = Weights::standard();
+
+    let alignment = weights.align(&wa::word("ka,-pa,-ze~"), &wa::word("ka,-ze~-lo`"));
+    assert_eq!(
+        alignment.edits,
+        [
+            Edit::Keep(wa::syllable("ka,")),
+            Edit::Delete(wa::syllable("pa,")),
+            Edit::Keep(wa::syllable("ze~")),
+            Edit::Insert(wa::syllable("lo`")),
+        ]
+    );
+    assert_eq!(alignment.distance, 2. * weights.syllable);
+
+    let alignment = weights.align(&wa::word("ka,-pa,"), &wa::word("ka,-ba,"));
+    assert_eq!(
+        alignment.edits,
+        [
+            Edit::Keep(wa::syllable("ka,")),
+            Edit::Substitute(wa::syllable("pa,"), wa::syllable("ba,")),
+        ]
+    );
+}
+
+// i * syllable and the sum of i syllables differ in the last bit for weights like 0.1
+#[test]
+fn aligning_with_nothing_walks_back_to_the_start() {
+    let weights = Weights {
+        syllable: 0.1,
+        ..Weights::default()
+    };
+    let word = wa::word("ka,-pa,-ta,-sa,-ra,-ba,");
+
+    let alignment = weights.align(&word, &Word(vec![]));
+    assert_eq!(alignment.edits.len(), 6);
+    assert!(alignment
+        .edits
+        .iter()
+        .all(|edit| matches!(edit, Edit::Delete(_))));
+
+    let alignment = weights.align(&Word(vec![]), &word);
+    assert!(alignment
+        .edits
+        .iter()
+        .all(|edit| matches!(edit, Edit::Insert(_))));
+}
+
+#[test]
+fn nearest_orders_by_distance_and_keeps_ties() {
+    let weights = Weights::standard();
+    let query = wa::syllable("ta,");
+    // pa, and ka, differ from ta, by place alone
+    let candidates = [
+        wa::syllable("ze~"),
+        wa::syllable("pa,"),
+        wa::syllable("ta,"),
+        wa::syllable("ka,"),
+        wa::syllable("da,"),
+    ];
+
+    let nearest: Vec<_> = weights
+        .nearest(&query, &candidates, 4)
+        .into_iter()
+        .map(|(syllable, _)| *syllable)
+        .collect();
+
+    assert_eq!(
+        nearest,
+        [
+            wa::syllable("ta,"),
+            wa::syllable("da,"),
+            wa::syllable("pa,"),
+            wa::syllable("ka,"),
+        ]
+    );
+}
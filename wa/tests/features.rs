@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use wa::{
+    features::{Backness, Class, Height, Manner, Phoneme, Segment, UnknownFeature},
+    H,
+};
+
+fn names<X: ToString>(xs: Vec<X>) -> Vec<String> {
+    xs.iter().map(ToString::to_string).collect()
+}
+
+// sound changes find a phoneme by its features, so no two can share them
+#[test]
+fn every_segment_has_its_own_features() {
+    let segments: Vec<Segment> = Segment::all().collect();
+    let features: HashSet<_> = segments.iter().map(Phoneme::features).collect();
+
+    assert_eq!(features.len(), segments.len());
+
+    let codas: HashSet<_> = H::all().map(|h| h.features()).collect();
+    assert_eq!(codas.len(), H::COUNT);
+}
+
+#[test]
+fn standard_classes() {
+    assert_eq!(
+        names(Class::OBSTRUENTS.onsets()),
+        ["x", "h", "p", "t", "k", "s", "b", "d", "g", "z", "py", "ty", "ky", "sy"]
+    );
+    assert_eq!(names(Class::OBSTRUENTS.codas()), ["x", "h"]);
+    assert!(Class::OBSTRUENTS.vowels().is_empty());
+
+    assert_eq!(names(Class::SONORANTS.onsets()), ["w", "y", "r", "rw", "l"]);
+    assert_eq!(names(Class::SONORANTS.codas()), ["w", "y"]);
+    assert!(Class::SONORANTS.vowels().is_empty());
+
+    assert!(Class::VOWELS.onsets().is_empty());
+    assert_eq!(names(Class::VOWELS.vowels()), ["a", "e", "i", "o", "u"]);
+
+    assert_eq!(
+        names(Class::PLOSIVES.onsets()),
+        ["p", "t", "k", "b", "d", "g", "py", "ty", "ky"]
+    );
+    assert_eq!(names(Class::ASPIRATED.onsets()), ["p", "t", "k"]);
+    assert_eq!(names(Class::PALATALIZED.onsets()), ["py", "ty", "ky", "sy"]);
+    assert_eq!(names(Class::GLIDES.codas()), ["w", "y"]);
+}
+
+#[test]
+fn classes_are_read_from_features() {
+    let class: Class = "plosive -voiced +palatalized".parse().unwrap();
+
+    assert_eq!(
+        class,
+        Class {
+            manner: Some(Manner::Plosive),
+            voiced: Some(false),
+            palatalized: Some(true),
+            ..Class::ANY
+        }
+    );
+    assert_eq!(names(class.onsets()), ["py", "ty", "ky"]);
+
+    let class: Class = "close back +rounded".parse().unwrap();
+    assert_eq!(
+        class,
+        Class {
+            height: Some(Height::Close),
+            backness: Some(Backness::Back),
+            rounded: Some(true),
+            ..Class::ANY
+        }
+    );
+    assert_eq!(names(class.vowels()), ["u"]);
+    assert_eq!(names(class.codas()), ["w"]);
+
+    assert_eq!("".parse::<Class>(), Ok(Class::ANY));
+    assert_eq!("obstruent".parse::<Class>(), Ok(Class::OBSTRUENTS));
+}
+
+#[test]
+fn unknown_features_are_rejected() {
+    assert_eq!(
+        "plosive +nasal".parse::<Class>(),
+        Err(UnknownFeature("+nasal".to_owned()))
+    );
+    assert_eq!(
+        "retroflex".parse::<Class>(),
+        Err(UnknownFeature("retroflex".to_owned()))
+    );
+    // signs are only for the binary features
+    assert_eq!(
+        "+plosive".parse::<Class>(),
+        Err(UnknownFeature("+plosive".to_owned()))
+    );
+    assert_eq!(
+        UnknownFeature("retroflex".to_owned()).to_string(),
+        "unknown feature 'retroflex'"
+    );
+}
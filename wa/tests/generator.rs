@@ -0,0 +1,139 @@
+use rand::{rngs::StdRng, SeedableRng};
+use wa::{
+    phonotactics::{Constraint, Pattern, Phonotactics, Rule},
+    CStem, ConfigError, Generator, GeneratorConfig, Length, PureC, Random, Text, C, H, T,
+};
+
+fn seeded() -> GeneratorConfig {
+    GeneratorConfig {
+        seed: Some(1),
+        ..GeneratorConfig::default()
+    }
+}
+
+#[test]
+fn uniform_length_with_min_above_max_is_rejected() {
+    let config = GeneratorConfig {
+        word_length: Length::Uniform { min: 3, max: 2 },
+        ..seeded()
+    };
+
+    assert_eq!(config.validate(), Err(ConfigError::Range("word_length")));
+    assert!(Generator::new(config).is_err());
+}
+
+#[test]
+fn weights_that_add_up_to_nothing_are_rejected() {
+    let config = GeneratorConfig {
+        tones: T::all().map(|tone| (tone, 0.)).collect(),
+        ..seeded()
+    };
+    assert_eq!(config.validate(), Err(ConfigError::Weights("tones")));
+
+    let config = GeneratorConfig {
+        phrase_length: Length::Weights(vec![-1., 0.]),
+        ..seeded()
+    };
+    assert_eq!(
+        config.validate(),
+        Err(ConfigError::Weights("phrase_length"))
+    );
+}
+
+#[test]
+fn phonotactics_that_forbid_everything_are_rejected() {
+    let config = GeneratorConfig {
+        phonotactics: Phonotactics::new()
+            .rule(Rule::new("nothing", Constraint::Syllable(Pattern::any()))),
+        ..seeded()
+    };
+
+    assert_eq!(config.validate(), Err(ConfigError::NoSyllable(None)));
+}
+
+#[test]
+fn phonotactics_that_leave_a_syllable_nothing_to_follow_it_are_rejected() {
+    let high = Pattern::any().tone([T::High]);
+    let config = GeneratorConfig {
+        phonotactics: Phonotactics::new().rule(Rule::new(
+            "nothing-after-high",
+            Constraint::Sequence(high.clone(), Pattern::any()),
+        )),
+        ..seeded()
+    };
+
+    assert!(matches!(
+        config.validate(),
+        Err(ConfigError::NoSyllable(Some(syllable))) if high.matches(&syllable)
+    ));
+
+    // fine when words never have a second syllable
+    let config = GeneratorConfig {
+        word_length: Length::Fixed(1),
+        ..config
+    };
+    assert_eq!(config.validate(), Ok(()));
+}
+
+#[test]
+fn strict_phonotactics_still_generate() {
+    // only ka^ is allowed, which rejection sampling alone rarely finds
+    let onsets: Vec<C> = C::all().filter(|onset| onset.to_string() != "k").collect();
+    let config = GeneratorConfig {
+        phonotactics: Phonotactics::new()
+            .rule(Rule::new(
+                "onset",
+                Constraint::Syllable(Pattern::any().onset(onsets)),
+            ))
+            .rule(Rule::new(
+                "vowel",
+                Constraint::Syllable(
+                    Pattern::any().vowel(wa::V::all().filter(|v| v.to_string() != "a")),
+                ),
+            ))
+            .rule(Rule::new(
+                "tone",
+                Constraint::Syllable(Pattern::any().tone([T::High, T::Low, T::Nasal])),
+            ))
+            .rule(Rule::new(
+                "coda",
+                Constraint::Syllable(Pattern::any().coda(wa::H::all().map(Some))),
+            )),
+        ..seeded()
+    };
+
+    let mut generator = Generator::new(config).unwrap();
+    for _ in 0..20 {
+        assert_eq!(generator.syllable(), wa::syllable("ka^"));
+    }
+}
+
+#[test]
+fn the_same_seed_makes_the_same_text() {
+    for seed in 0..10 {
+        let mut a = StdRng::seed_from_u64(seed);
+        let mut b = StdRng::seed_from_u64(seed);
+
+        assert_eq!(Text::random(&mut a), Text::random(&mut b));
+        assert_eq!(PureC::random(&mut a), PureC::random(&mut b));
+        assert_eq!(H::random(&mut a), H::random(&mut b));
+    }
+}
+
+#[test]
+fn random_segments_follow_the_weights() {
+    let mut rng = StdRng::seed_from_u64(1);
+
+    // H onsets and empty codas are never drawn, however heavy
+    let config = GeneratorConfig {
+        onsets: vec![(C::H(H::H), 100.), (C::C(PureC::Sharp(CStem::T)), 1.)],
+        codas: vec![(None, 100.), (Some(H::X), 1.)],
+        ..seeded()
+    };
+
+    for _ in 0..20 {
+        assert_eq!(config.consonant(&mut rng), PureC::Sharp(CStem::T));
+        assert_eq!(config.stem(&mut rng), CStem::T);
+        assert_eq!(config.h(&mut rng), H::X);
+    }
+}
@@ -0,0 +1,36 @@
+use wa::{Ipa, IpaOptions, T};
+
+#[test]
+fn tones_are_chao_letters() {
+    assert_eq!(T::High.ipa(), "˥");
+    assert_eq!(T::Low.ipa(), "˩");
+    assert_eq!(T::Peaking.ipa(), "˧˥˧");
+}
+
+#[test]
+fn a_nasal_tone_is_only_written_with_its_pitch_when_asked() {
+    assert_eq!(T::Nasal.ipa(), "\u{0303}");
+    assert_eq!(T::Nasal.ipa_with(&IpaOptions::narrow()), "\u{0303}");
+
+    let options = IpaOptions {
+        nasal_pitch: true,
+        ..IpaOptions::narrow()
+    };
+    assert_eq!(T::Nasal.ipa_with(&options), "\u{0303}˩");
+
+    // a syllable has both either way
+    assert_eq!(wa::syllable("ze~").ipa(), "z\u{033A}e\u{031E}\u{0303}˩");
+}
+
+#[test]
+fn ascii_is_ipa_with_ascii_letters() {
+    let options = IpaOptions {
+        ascii: true,
+        ..IpaOptions::narrow()
+    };
+
+    assert_eq!(
+        wa::sentence("rwa, ka^h-pa,?").ipa_with(&options),
+        "Ra55 khap353.pha55?"
+    );
+}
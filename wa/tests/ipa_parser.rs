@@ -0,0 +1,86 @@
+use wa::{Detail, FromIpa, Generator, GeneratorConfig, Ipa, IpaOptions, Syllable, Text, ToneStyle};
+
+// every way `Ipa` can write a transcription
+fn styles() -> Vec<IpaOptions> {
+    let mut styles = Vec::new();
+
+    for detail in [Detail::Narrow, Detail::Broad] {
+        for tones in [ToneStyle::Letters, ToneStyle::Numbers] {
+            for ascii in [false, true] {
+                for delimit in [false, true] {
+                    styles.push(IpaOptions {
+                        detail,
+                        tones,
+                        ascii,
+                        delimit,
+                        ..IpaOptions::default()
+                    });
+                }
+            }
+        }
+    }
+
+    styles
+}
+
+#[test]
+fn every_syllable_round_trips() {
+    for options in styles() {
+        for syllable in Syllable::all() {
+            let ipa = syllable.ipa_with(&options);
+
+            assert_eq!(Syllable::from_ipa(&ipa), Ok(syllable), "{ipa} {options:?}");
+        }
+    }
+}
+
+#[test]
+fn generated_texts_round_trip() {
+    let mut generator = Generator::new(GeneratorConfig {
+        seed: Some(5),
+        ..GeneratorConfig::default()
+    })
+    .unwrap();
+
+    for _ in 0..50 {
+        let text = generator.text();
+
+        for options in styles() {
+            let ipa = text.ipa_with(&options);
+
+            assert_eq!(Text::from_ipa(&ipa), Ok(text.clone()), "{ipa} {options:?}");
+        }
+    }
+}
+
+#[test]
+fn aspiration_can_be_h() {
+    assert_eq!(Syllable::from_ipa("pha˥"), Ok(wa::syllable("pa,")));
+    assert_eq!(Syllable::from_ipa("tha˥"), Ok(wa::syllable("ta,")));
+}
+
+#[test]
+fn glides_can_be_j_and_w() {
+    assert_eq!(Syllable::from_ipa("ja˩"), Ok(wa::syllable("ya`")));
+    assert_eq!(Syllable::from_ipa("wa˩"), Ok(wa::syllable("wa`")));
+    assert_eq!(Syllable::from_ipa("paj˥"), Ok(wa::syllable("pa,y")));
+}
+
+#[test]
+fn tones_can_be_chao_numbers() {
+    assert_eq!(Syllable::from_ipa("pa55"), Ok(wa::syllable("pa,")));
+    assert_eq!(Syllable::from_ipa("pa⁵⁵"), Ok(wa::syllable("pa,")));
+    assert_eq!(Syllable::from_ipa("pa353"), Ok(wa::syllable("pa^")));
+}
+
+#[test]
+fn transcriptions_can_be_delimited() {
+    assert_eq!(Syllable::from_ipa("/pa˥/"), Ok(wa::syllable("pa,")));
+    assert_eq!(Syllable::from_ipa("[pa˥]"), Ok(wa::syllable("pa,")));
+}
+
+#[test]
+fn blunt_r_can_be_capital_r() {
+    assert_eq!(Syllable::from_ipa("Ra˥"), Ok(wa::syllable("rwa,")));
+    assert_eq!(Syllable::from_ipa("Ra˥"), Syllable::from_ipa("ɹa˥"));
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+
+use rand::{rngs::StdRng, SeedableRng};
+use wa::{markov::Model, serde::Compact, Syllable};
+
+fn model() -> Model {
+    Model::trained_on(&[
+        wa::text("ka,-pa`-ze~ ka,-ze~. tyo^-ka,!"),
+        wa::text("ka,-pa`? to^x-ka,-ka,"),
+    ])
+}
+
+#[test]
+fn a_model_round_trips_through_json() {
+    let model = model();
+    let json = serde_json::to_string(&model).unwrap();
+
+    assert_eq!(serde_json::from_str::<Model>(&json).unwrap(), model);
+
+    // every field has a default
+    assert_eq!(serde_json::from_str::<Model>("{}").unwrap(), Model::new());
+}
+
+#[test]
+fn the_same_seed_makes_the_same_text() {
+    let model = model();
+
+    for seed in 0..10 {
+        let mut a = StdRng::seed_from_u64(seed);
+        let mut b = StdRng::seed_from_u64(seed);
+
+        assert_eq!(model.text(&mut a), model.text(&mut b));
+    }
+}
+
+#[test]
+fn words_start_and_continue_like_the_training_words() {
+    let model = model();
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for _ in 0..200 {
+        let word = model.word(&mut rng);
+
+        assert!(model.starts.contains_key(&Compact(word[0])), "{word}");
+        assert!(model.word_lengths.contains_key(&word.len()), "{word}");
+
+        // a syllable that was never followed by anything is followed by one of the seen ones,
+        // perhaps with another tone
+        for pair in word.windows(2) {
+            let next = pair[1];
+
+            match model.transitions.get(&Compact(pair[0])) {
+                Some(seen) => assert!(seen.contains_key(&Compact(next)), "{word}"),
+                None => assert!(
+                    model.syllables.keys().any(|Compact(seen)| Syllable {
+                        tone: next.tone,
+                        ..*seen
+                    } == next),
+                    "{word}"
+                ),
+            }
+        }
+    }
+}
+
+#[test]
+fn a_model_without_syllables_is_empty() {
+    assert!(Model::new().is_empty());
+    assert!(!model().is_empty());
+
+    let model = Model {
+        starts: BTreeMap::from([(Compact(wa::syllable("ka,")), 1)]),
+        ..Model::new()
+    };
+    assert!(model.is_empty());
+
+    let model = Model {
+        syllables: BTreeMap::from([(Compact(wa::syllable("ka,")), 1)]),
+        ..Model::new()
+    };
+    assert!(model.is_empty());
+}
@@ -0,0 +1,164 @@
+use wa::{numerals::Numerals, Expected, Word};
+
+// the standard digits and powers up to a thousand, with one ten written out
+const LONG_FORM: &str = "
+0 = ze~
+1 = ka,
+2 = tu`
+3 = sa^
+4 = pi~
+5 = lo,
+6 = bu^
+7 = si`
+8 = ho,
+9 = wa~
+10 = te^x
+100 = ku`y
+1000 = pyo,h
+";
+
+fn numbers() -> impl Iterator<Item = u64> {
+    (0..=12_000).chain([
+        1_000_000,
+        1_000_001,
+        987_654_321,
+        1_000_000_000,
+        u64::MAX / 7,
+        u64::MAX,
+    ])
+}
+
+#[test]
+fn words_are_read_back() {
+    let numerals = Numerals::standard();
+
+    for n in numbers() {
+        let word = numerals.word(n);
+
+        assert_eq!(numerals.word_value(&word), Some(n), "{word}");
+    }
+}
+
+#[test]
+fn phrases_are_read_back() {
+    let numerals = Numerals::standard();
+
+    for n in numbers() {
+        let phrase = numerals.phrase(n);
+
+        assert_eq!(numerals.phrase_value(&phrase), Some(n), "{phrase}");
+    }
+}
+
+#[test]
+fn standard_spellings() {
+    let numerals = Numerals::standard();
+
+    assert_eq!(numerals.word(0), wa::word("ze~"));
+    assert_eq!(numerals.word(10), wa::word("te^x"));
+    assert_eq!(numerals.word(20), wa::word("tu`-te^x"));
+    assert_eq!(numerals.word(1234), wa::word("pyo,h-tu`-ku`y-sa^-te^x-pi~"));
+    assert_eq!(
+        numerals.phrase(1234),
+        wa::phrase("pyo,h tu`-ku`y sa^-te^x pi~")
+    );
+}
+
+#[test]
+fn one_is_omitted_before_a_power() {
+    let standard = Numerals::standard();
+
+    // one ten is not how ten is spelled
+    assert_eq!(standard.word_value(&wa::word("te^x")), Some(10));
+    assert_eq!(standard.word_value(&wa::word("ka,-te^x")), None);
+    assert_eq!(standard.word_value(&wa::word("ka,-ku`y")), None);
+    assert_eq!(standard.phrase_value(&wa::phrase("ka,-te^x")), None);
+
+    // and without omit-one it is
+    let long_form: Numerals = LONG_FORM.parse().unwrap();
+
+    assert_eq!(long_form.word(10), wa::word("ka,-te^x"));
+    assert_eq!(long_form.word_value(&wa::word("ka,-te^x")), Some(10));
+    assert_eq!(long_form.word_value(&wa::word("te^x")), None);
+}
+
+#[test]
+fn words_that_are_not_numbers() {
+    let numerals = Numerals::standard();
+
+    // two digits without a power, a word that is no numeral and a phrase split differently from
+    // `phrase`
+    assert_eq!(numerals.word_value(&wa::word("ka,-tu`")), None);
+    assert_eq!(numerals.word_value(&wa::word("ba^")), None);
+    assert_eq!(numerals.phrase_value(&wa::phrase("sa^ te^x")), None);
+}
+
+#[test]
+fn a_value_with_two_words_is_rejected() {
+    let error = "0 = ze~\n1 = ka,\n1 = tu`\n10 = te^x"
+        .parse::<Numerals>()
+        .unwrap_err();
+
+    assert_eq!(error.line, 3);
+    assert_eq!(error.message, "1 already has a word");
+}
+
+#[test]
+fn a_word_with_two_values_is_rejected() {
+    let error = "0 = ze~\n1 = ka,\n\n10 = ka,"
+        .parse::<Numerals>()
+        .unwrap_err();
+
+    assert_eq!(error.line, 4);
+    assert!(error.message.contains("already the word for 1"), "{error}");
+}
+
+#[test]
+fn a_base_below_two_is_rejected() {
+    // only 0 and 1 run from 0, so there's no power to count with
+    let error = "0 = ze~\n1 = ka,\n10 = te^x"
+        .parse::<Numerals>()
+        .unwrap_err();
+
+    assert_eq!(error.line, 3);
+    assert_eq!(
+        error.message,
+        "expected words for 0, 1 and the smallest power"
+    );
+
+    // nothing for 0
+    assert!("1 = ka,\n2 = tu`\n10 = te^x".parse::<Numerals>().is_err());
+}
+
+#[test]
+fn malformed_lines_are_rejected() {
+    assert_eq!("0 ze~".parse::<Numerals>().unwrap_err().line, 1);
+    assert_eq!(
+        "# digits\nten = te^x".parse::<Numerals>().unwrap_err().line,
+        2
+    );
+    assert_eq!("0 = qq".parse::<Numerals>().unwrap_err().line, 1);
+}
+
+#[test]
+fn the_escape_spells_numbers_out() {
+    assert_eq!(wa::word("#1234"), wa::word("pyo,h-tu`-ku`y-sa^-te^x-pi~"));
+    assert_eq!(wa::word("#10-ka,"), wa::word("te^x-ka,"));
+
+    // anything parsed as text gets it, like what dux renders
+    assert_eq!(wa::text("#20 ka,. #0"), wa::text("tu`-te^x ka,. ze~"));
+}
+
+#[test]
+fn the_escape_needs_digits() {
+    let error = "#12a".parse::<Word>().unwrap_err();
+    assert_eq!(error.expected, Expected::Number);
+    assert_eq!(error.span, 3..4);
+    assert_eq!(error.found, Some('a'));
+
+    assert_eq!("#".parse::<Word>().unwrap_err().expected, Expected::Number);
+
+    let error = "#99999999999999999999".parse::<Word>().unwrap_err();
+    assert_eq!(error.expected, Expected::Number);
+    assert!(error.note.is_some());
+}
@@ -0,0 +1,69 @@
+use wa::{Alphabet, Generator, GeneratorConfig, Phonetic, Syllable, C, H, T, V};
+
+const ALPHABETS: [Alphabet; 2] = [Alphabet::XSampa, Alphabet::Kirshenbaum];
+
+fn spelled(phonetic: &str) -> bool {
+    !phonetic.is_empty() && phonetic.is_ascii() && !phonetic.contains('?')
+}
+
+#[test]
+fn every_phoneme_has_a_spelling() {
+    for alphabet in ALPHABETS {
+        for c in C::all() {
+            assert!(spelled(&c.phonetic(alphabet)), "{c:?}");
+        }
+        for h in H::all() {
+            assert!(spelled(&h.phonetic(alphabet)), "{h:?}");
+        }
+        for v in V::all() {
+            assert!(spelled(&v.phonetic(alphabet)), "{v:?}");
+        }
+        for t in T::all() {
+            assert!(spelled(&t.phonetic(alphabet)), "{t:?}");
+        }
+    }
+}
+
+#[test]
+fn every_syllable_has_a_spelling() {
+    for alphabet in ALPHABETS {
+        for syllable in Syllable::all() {
+            assert!(spelled(&syllable.phonetic(alphabet)), "{syllable}");
+        }
+    }
+}
+
+// what the standard sandhi rules write has to be spelled too
+#[test]
+fn texts_have_a_spelling() {
+    let mut generator = Generator::new(GeneratorConfig {
+        seed: Some(3),
+        ..GeneratorConfig::default()
+    })
+    .unwrap();
+
+    for _ in 0..50 {
+        let text = generator.text();
+
+        for alphabet in ALPHABETS {
+            let phonetic = text.phonetic(alphabet);
+            assert!(phonetic.is_ascii() && !phonetic.contains('?'), "{phonetic}");
+        }
+    }
+}
+
+#[test]
+fn spellings() {
+    // the quotation is between dollar signs, as quotation marks are already vowel diacritics
+    let sentence = wa::sentence("ka^h-pa^ ka,w-wa^: \"ta`-xi~y\" (rwu,)?");
+
+    assert_eq!(
+        sentence.x_sampa(),
+        "k_ha_\"p_}_M_T.p_ha_\"_M_T_M k_ha_\"_T.u_^_O:a_\"_M_T_M, \
+         $t_a_ha_\"_B.xi~i_^_c_B$ (r\\u_T)<R>"
+    );
+    assert_eq!(
+        sentence.kirshenbaum(),
+        "k<h>a\"p<unx>35.p<h>a\"353 k<h>a\"55.w:a\"353, $t<h>a\"11.xi~j11$ (ru55)<R>"
+    );
+}
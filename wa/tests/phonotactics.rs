@@ -0,0 +1,135 @@
+use wa::{
+    phonotactics::{Constraint, Pattern, Phonotactics, Rule, Violation},
+    Expected, Generator, GeneratorConfig, ParseMode, Path, Text, C, H, T,
+};
+
+// the bans the language has started making
+fn bans() -> Phonotactics {
+    Phonotactics::new()
+        .rule(Rule::new(
+            "y onset with y coda",
+            Constraint::Syllable(Pattern::any().onset([C::H(H::Y)]).coda([Some(H::Y)])),
+        ))
+        .rule(Rule::new(
+            "nasal tone with x coda",
+            Constraint::Syllable(Pattern::any().tone([T::Nasal]).coda([Some(H::X)])),
+        ))
+        .rule(Rule::new(
+            "nasal tone after nasal tone",
+            Constraint::Sequence(
+                Pattern::any().tone([T::Nasal]),
+                Pattern::any().tone([T::Nasal]),
+            ),
+        ))
+}
+
+fn violation(rule: &str, path: Path, syllables: usize) -> Violation {
+    Violation {
+        rule: rule.to_owned(),
+        path,
+        syllables,
+    }
+}
+
+#[test]
+fn a_y_onset_cannot_have_a_y_coda() {
+    let bans = bans();
+
+    assert_eq!(
+        bans.check(&wa::syllable("ya,y")),
+        Err(violation("y onset with y coda", Path::default(), 1))
+    );
+    assert_eq!(bans.check(&wa::syllable("ya,w")), Ok(()));
+    assert_eq!(bans.check(&wa::syllable("ka,y")), Ok(()));
+}
+
+#[test]
+fn a_nasal_tone_cannot_have_an_x_coda() {
+    let bans = bans();
+
+    assert_eq!(
+        bans.check(&wa::syllable("ka~x")),
+        Err(violation("nasal tone with x coda", Path::default(), 1))
+    );
+    assert_eq!(bans.check(&wa::syllable("ka,x")), Ok(()));
+    assert_eq!(bans.check(&wa::syllable("ka~")), Ok(()));
+}
+
+#[test]
+fn sequences_are_only_forbidden_within_a_word() {
+    let bans = bans();
+
+    assert_eq!(
+        bans.check(&wa::word("ka,-ze~-lo~")),
+        Err(violation(
+            "nasal tone after nasal tone",
+            Path::default().syllable(1),
+            2
+        ))
+    );
+    assert_eq!(bans.check(&wa::word("ze~-ka,-lo~")), Ok(()));
+    assert_eq!(bans.check(&wa::phrase("ze~ lo~")), Ok(()));
+}
+
+#[test]
+fn violations_have_the_path_of_the_syllable() {
+    let violations = bans().check(&wa::text("ka,. pa, ya,y"));
+
+    assert_eq!(
+        violations,
+        Err(violation(
+            "y onset with y coda",
+            Path::default()
+                .paragraph(0)
+                .sentence(1)
+                .phrase(0)
+                .word(1)
+                .syllable(0),
+            1
+        ))
+    );
+}
+
+#[test]
+fn the_parser_rejects_forbidden_syllables() {
+    let bans = bans();
+    let input = "pa, ka,-ze~-lo~";
+
+    let error = bans.parse::<Text>(input, ParseMode::Strict).unwrap_err();
+
+    assert_eq!(error.expected, Expected::Permitted);
+    assert_eq!(&input[error.span.clone()], "ze~-lo~");
+    assert_eq!(
+        error.note.as_deref(),
+        Some("rule 'nasal tone after nasal tone' forbids it")
+    );
+
+    assert_eq!(
+        bans.parse::<Text>("pa, ze~ lo~", ParseMode::Strict),
+        Ok(wa::text("pa, ze~ lo~"))
+    );
+}
+
+#[test]
+fn the_generator_keeps_to_the_rules() {
+    let mut generator = Generator::new(GeneratorConfig {
+        seed: Some(7),
+        phonotactics: bans(),
+        ..GeneratorConfig::default()
+    })
+    .unwrap();
+
+    for _ in 0..50 {
+        let text = generator.text();
+
+        assert_eq!(bans().check(&text), Ok(()), "{text}");
+    }
+}
+
+// the rules a `--phonotactics` file holds
+#[test]
+fn rules_are_read_from_json() {
+    let json = serde_json::to_string(&bans()).unwrap();
+
+    assert_eq!(serde_json::from_str::<Phonotactics>(&json).unwrap(), bans());
+}
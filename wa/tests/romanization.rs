@@ -0,0 +1,75 @@
+use wa::{
+    romanization::{Ascii, Numbered, Romanization, Romanize, Unicode},
+    Enclosure, Expected, Sentence, Span, Syllable, Word,
+};
+
+fn nested() -> Sentence {
+    let mut sentence = wa::sentence("ka^ xu,y ze~ lo~: ba` tyo`w?");
+
+    // “ka^ “xu,y (ze~)” lo~”: (ba` tyo`w)?
+    sentence.spans = vec![
+        Span {
+            enclosure: Enclosure::Quotation,
+            start: 0,
+            end: 4,
+        },
+        Span {
+            enclosure: Enclosure::Quotation,
+            start: 1,
+            end: 3,
+        },
+        Span {
+            enclosure: Enclosure::Parenthetical,
+            start: 2,
+            end: 3,
+        },
+        Span {
+            enclosure: Enclosure::Parenthetical,
+            start: 4,
+            end: 6,
+        },
+    ];
+
+    sentence
+}
+
+#[test]
+fn nested_enclosures_round_trip() {
+    let sentence = nested();
+    let schemes: [&dyn Romanization; 3] = [&Unicode, &Ascii, &Numbered];
+
+    for scheme in schemes {
+        let romanized = sentence.romanize(scheme);
+        let parsed: Sentence = wa::parse(&romanized).unwrap();
+
+        assert_eq!(parsed, sentence, "{romanized}");
+    }
+}
+
+#[test]
+fn quotes_open_and_close_by_what_comes_before() {
+    let sentence = nested();
+
+    assert_eq!(
+        sentence.romanize(&Ascii),
+        "\"ka^ \"xu,y (ze~)\" lo~\": (ba` tyo`w)?"
+    );
+}
+
+#[test]
+fn a_digit_after_a_tone_is_unexpected() {
+    assert_eq!(wa::parse::<Syllable>("ka1"), Ok(wa::syllable("ka,")));
+    assert_eq!(wa::parse::<Syllable>("ka4x"), Ok(wa::syllable("ka~x")));
+
+    let error = wa::parse::<Syllable>("ka,1").unwrap_err();
+    assert_eq!(error.expected, Expected::End);
+    assert_eq!(error.span, 3..4);
+    assert_eq!(error.message(), "unexpected digit '1'");
+
+    let error = wa::parse::<Word>("ka`x2-pa,").unwrap_err();
+    assert_eq!(error.message(), "unexpected digit '2'");
+
+    // a second diacritic is still a doubled mark
+    let error = wa::parse::<Syllable>("ka,`").unwrap_err();
+    assert_eq!(error.message(), "doubled tone mark '`'");
+}
@@ -0,0 +1,165 @@
+use wa::{
+    phonotactics::Pattern,
+    sandhi::{Change, Edit, Sandhi, SandhiRule, Scope, Side, Slot, Transcribe},
+    CStem, Ipa, PureC, C, H, T,
+};
+
+fn error(rules: &str) -> (usize, String) {
+    let error = rules.parse::<Sandhi>().unwrap_err();
+
+    (error.line, error.message)
+}
+
+#[test]
+fn the_standard_rules_are_read() {
+    let names: Vec<&str> = Sandhi::standard()
+        .rules
+        .iter()
+        .map(|rule| rule.name.as_str())
+        .collect();
+
+    assert_eq!(
+        names,
+        [
+            "peaking-before-peaking",
+            "h-before-labial",
+            "h-before-dental",
+            "h-before-velar",
+            "w-before-w",
+            "y-before-y",
+        ]
+    );
+
+    assert_eq!(
+        Sandhi::standard().rules[1],
+        SandhiRule {
+            name: "h-before-labial".to_owned(),
+            scope: Scope::Phrase,
+            left: Pattern::any().coda([Some(H::H)]),
+            right: Pattern::any()
+                .onset([PureC::Strong, PureC::Blunt, PureC::Sharp].map(|c| C::C(c(CStem::P)))),
+            changes: vec![Change {
+                side: Side::Left,
+                slot: Slot::Coda,
+                edit: Edit::Replace("p̚".to_owned()),
+            }],
+        }
+    );
+
+    assert_eq!(
+        Sandhi::standard().rules[4].changes,
+        [
+            Change {
+                side: Side::Left,
+                slot: Slot::Coda,
+                edit: Edit::Replace(String::new()),
+            },
+            Change {
+                side: Side::Right,
+                slot: Slot::Onset,
+                edit: Edit::Append("ː".to_owned()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn rules_default_to_word_scope() {
+    let sandhi: Sandhi = "# only in words\nlong: _ + tone=high|, -> right.vowel += ː"
+        .parse()
+        .unwrap();
+
+    assert_eq!(sandhi.rules[0].scope, Scope::Word);
+    assert_eq!(sandhi.rules[0].left, Pattern::any());
+    assert_eq!(
+        sandhi.rules[0].right,
+        Pattern::any().tone([T::High, T::High])
+    );
+}
+
+// the last rule to change a slot wins, so a custom rule after the standard ones overrides them
+#[test]
+fn custom_rules_override_the_standard_ones() {
+    let word = wa::word("pa,h-pa,");
+    assert_eq!(word.transcribe(Sandhi::standard()), word.ipa());
+
+    let glottal: Sandhi = "phrase glottal: coda=h + onset=p -> left.coda = ʔ"
+        .parse()
+        .unwrap();
+    let sandhi = Sandhi {
+        rules: [Sandhi::standard().rules.clone(), glottal.rules].concat(),
+    };
+
+    let standard = word.transcribe(Sandhi::standard());
+    let custom = word.transcribe(&sandhi);
+    assert!(standard.contains("p̚"));
+    assert_eq!(custom, standard.replace("p̚", "ʔ"));
+
+    // across words too, since the rule is a phrase rule
+    let phrase = wa::phrase("pa,h pa,");
+    assert_eq!(phrase.transcribe(&sandhi), custom.replace('.', " "));
+}
+
+#[test]
+fn malformed_lines_are_reported_with_their_line() {
+    let cases = [
+        (
+            "a coda=h + _ -> left.coda = p",
+            "expected ':' after the rule's name",
+        ),
+        (
+            "clause a: coda=h + _ -> left.coda = p",
+            "unknown scope 'clause', expected word or phrase",
+        ),
+        (
+            "phrase a b: coda=h + _ -> left.coda = p",
+            "expected a name without spaces before ':'",
+        ),
+        (
+            "a: coda=h + _ left.coda = p",
+            "expected '->' before the changes",
+        ),
+        (
+            "a: coda=h _ -> left.coda = p",
+            "expected '+' between the left and right patterns",
+        ),
+        (
+            "a: coda + _ -> left.coda = p",
+            "expected a term like 'onset=p', found 'coda'",
+        ),
+        ("a: coda=q + _ -> left.coda = p", "unknown value 'q'"),
+        (
+            "a: nucleus=a + _ -> left.coda = p",
+            "unknown slot 'nucleus'",
+        ),
+        (
+            "a: coda=h + _ -> left.coda p",
+            "expected a change like 'left.coda = p̚', found ' left.coda p'",
+        ),
+        (
+            "a: coda=h + _ -> coda = p",
+            "expected a slot like 'left.coda', found 'coda'",
+        ),
+        (
+            "a: coda=h + _ -> middle.coda = p",
+            "unknown side 'middle', expected left or right",
+        ),
+        (
+            "a: coda=h + _ -> left.nucleus = p",
+            "unknown slot 'nucleus'",
+        ),
+    ];
+
+    for (line, message) in cases {
+        assert_eq!(
+            error(&format!("# fine\n\n{line}")),
+            (3, message.to_owned()),
+            "{line}"
+        );
+    }
+
+    assert_eq!(
+        error("a: coda=h + _ -> left.coda = p\nb: coda=h + _ -> left.coda = p, coda = x").0,
+        2
+    );
+}
@@ -0,0 +1,188 @@
+use wa::{
+    features::Class,
+    sandhi::Slot,
+    sound_change::{Condition, Develop, History, Neighbour, Term, Value},
+    T,
+};
+
+fn history(rules: &str) -> History {
+    rules.parse().unwrap()
+}
+
+fn error(rules: &str) -> (usize, String) {
+    let error = rules.parse::<History>().unwrap_err();
+
+    (error.line, error.message)
+}
+
+#[test]
+fn changes_apply_in_order() {
+    let forwards = history("a: onset=p -> b\nb: onset=b -> d");
+    let backwards = history("b: onset=b -> d\na: onset=p -> b");
+
+    assert_eq!(forwards.apply(&wa::word("pa,")), wa::word("da,"));
+    assert_eq!(backwards.apply(&wa::word("pa,")), wa::word("ba,"));
+}
+
+#[test]
+fn environments_limit_where_a_change_applies() {
+    // voicing before a high tone, word-initially
+    let voicing = history("voicing: onset=p|t|k -> b|d|g / initial tone=high");
+
+    assert_eq!(voicing.apply(&wa::word("pa,-ta,")), wa::word("ba,-ta,"));
+    assert_eq!(voicing.apply(&wa::word("ka`-ta,")), wa::word("ka`-ta,"));
+
+    let raising = history("raising: vowel=e -> i / !final next.tone=nasal");
+    assert_eq!(
+        raising.apply(&wa::word("pe,-ke~-se~")),
+        wa::word("pi,-ki~-se~")
+    );
+
+    let lenition = history("lenition: onset[plosive] -> h / prev.coda=none !initial");
+    assert_eq!(
+        lenition.apply(&wa::word("ka,-ka,x-ka,")),
+        wa::word("ka,-ha,x-ka,")
+    );
+}
+
+#[test]
+fn a_change_does_not_feed_itself() {
+    // every low tone after a high one rises, but only the ones that were after a high tone before
+    let spreading = history("spreading: tone=low -> high / prev.tone=high");
+
+    assert_eq!(
+        spreading.apply(&wa::word("ka,-ka`-ka`")),
+        wa::word("ka,-ka,-ka`")
+    );
+}
+
+#[test]
+fn derivations_only_have_the_changes_that_did_something() {
+    let history = history(
+        "
+        voicing: onset=p|t|k -> b|d|g / initial tone=high
+        debuccalisation: coda=x -> h
+        raising: vowel=a -> e / next.tone=nasal
+        ",
+    );
+
+    let derivation = history.derive(&wa::word("pa,-ka~"));
+    let changes: Vec<&str> = derivation
+        .steps
+        .iter()
+        .map(|step| step.change.as_str())
+        .collect();
+
+    assert_eq!(changes, ["voicing", "raising"]);
+    assert_eq!(derivation.steps[0].word, wa::word("ba,-ka~"));
+    assert_eq!(*derivation.output(), wa::word("be,-ka~"));
+    assert_eq!(*derivation.output(), history.apply(&wa::word("pa,-ka~")));
+
+    // every word of a text gets one
+    let text = wa::text("pa,-ka~ ta,. ka`");
+    assert_eq!(text.derivations(&history).len(), 3);
+    assert_eq!(text.develop(&history), wa::text("be,-ka~ da,. ka`"));
+}
+
+#[test]
+fn features_change_only_into_phonemes_of_the_inventory() {
+    // a voiced aspirated stop isn't one, so +voiced alone leaves p alone
+    let voicing = history("voicing: onset[plosive -voiced] -> [+voiced]");
+    assert_eq!(voicing.apply(&wa::word("pa,")), wa::word("pa,"));
+
+    // nor is a voiced palatalised stop
+    let voicing = history("voicing: onset[plosive -voiced] -> [+voiced -aspirated]");
+    assert_eq!(
+        voicing.apply(&wa::word("pa,-pya,-ka`")),
+        wa::word("ba,-pya,-ga`")
+    );
+}
+
+#[test]
+fn conditions_are_read() {
+    let history = history("a: vowel=a -> o / !final prev.tone=high|low next.onset[plosive]");
+
+    assert_eq!(
+        history.changes[0].environment,
+        [
+            Condition::Not(Box::new(Condition::Final)),
+            Condition::Neighbour(
+                Neighbour::Previous,
+                Term::Is(vec![Value::Tone(T::High), Value::Tone(T::Low)])
+            ),
+            Condition::Neighbour(Neighbour::Next, Term::In(Slot::Onset, Class::PLOSIVES)),
+        ]
+    );
+}
+
+#[test]
+fn comments_and_blank_lines_are_skipped() {
+    let history = history("# voicing\n\na: onset=p -> b # before anything else\n");
+
+    assert_eq!(history.changes.len(), 1);
+    assert_eq!(history.changes[0].name, "a");
+}
+
+#[test]
+fn malformed_lines_are_reported_with_their_line() {
+    assert_eq!(
+        error("# first\n\na onset=p -> b"),
+        (3, "expected ':' after the change's name".to_owned())
+    );
+    assert_eq!(
+        error("two words: onset=p -> b"),
+        (1, "expected a name without spaces before ':'".to_owned())
+    );
+    assert_eq!(
+        error("a: onset=p b"),
+        (
+            1,
+            "expected '->' between the target and the replacement".to_owned()
+        )
+    );
+    assert_eq!(
+        error("a: onset=p -> b\nb: onset=p|t|k -> b|d"),
+        (2, "expected 1 or 3 replacements, found 2".to_owned())
+    );
+    assert_eq!(
+        error("a: onset=p -> b|d"),
+        (1, "expected 1 replacement, found 2".to_owned())
+    );
+    assert_eq!(
+        error("a: onset[plosive] -> b|d"),
+        (
+            1,
+            "expected a single replacement for a natural class".to_owned()
+        )
+    );
+    assert_eq!(
+        error("a: nucleus=a -> o"),
+        (1, "unknown slot 'nucleus'".to_owned())
+    );
+    assert_eq!(
+        error("a: onset=q -> b"),
+        (1, "unknown value 'q'".to_owned())
+    );
+    assert_eq!(
+        error("a: onset=p -> b / tone[high]"),
+        (1, "tones have no features".to_owned())
+    );
+    assert_eq!(
+        error("a: onset[nasal] -> b"),
+        (1, "unknown feature 'nasal'".to_owned())
+    );
+    assert_eq!(
+        error("a: onset[plosive] -> [sonorant]"),
+        (
+            1,
+            "a replacement can't change obstruent or sonorant alone, change the manner".to_owned()
+        )
+    );
+    assert_eq!(
+        error("a: onset=p -> b / prev.onset"),
+        (
+            1,
+            "expected a term like 'onset=p' or 'onset[plosive]', found 'onset'".to_owned()
+        )
+    );
+}
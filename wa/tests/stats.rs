@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+use wa::{
+    stats::{Stats, Tally},
+    Syllable, C, H, T, V,
+};
+
+fn stats() -> Stats {
+    wa::text("ka,-pa` ka,x. ka,-pa`").stats()
+}
+
+fn onset(s: &str) -> C {
+    C::all().find(|c| c.to_string() == s).unwrap()
+}
+
+#[test]
+fn a_text_is_tallied() {
+    let stats = stats();
+    let (ka, pa, kax) = (
+        wa::syllable("ka,"),
+        wa::syllable("pa`"),
+        wa::syllable("ka,x"),
+    );
+
+    assert_eq!(stats.word_count, 3);
+    assert_eq!(stats.syllable_count, 5);
+    assert_eq!(
+        stats.onsets,
+        BTreeMap::from([(onset("k"), 3), (onset("p"), 2)])
+    );
+    assert_eq!(stats.vowels, BTreeMap::from([(V::A, 5)]));
+    assert_eq!(stats.tones, BTreeMap::from([(T::High, 3), (T::Low, 2)]));
+    assert_eq!(stats.codas, BTreeMap::from([(None, 4), (Some(H::X), 1)]));
+    assert_eq!(
+        stats.syllables,
+        BTreeMap::from([(ka, 2), (pa, 2), (kax, 1)])
+    );
+    assert_eq!(stats.word_lengths, BTreeMap::from([(1, 1), (2, 2)]));
+    assert_eq!(stats.bigrams, BTreeMap::from([((ka, pa), 2)]));
+    assert_eq!(stats.bigram_count(), 2);
+    assert_eq!(stats.coverage(), 3. / Syllable::COUNT as f64);
+
+    // adding node by node gives the same as tallying the whole text
+    let mut added = Stats::new();
+    for paragraph in wa::text("ka,-pa` ka,x. ka,-pa`").iter() {
+        added.add(paragraph);
+    }
+    assert_eq!(added, stats);
+}
+
+#[test]
+fn precision_limits_the_rows() {
+    let all = stats().to_string();
+    let top = format!("{:.1}", stats());
+
+    assert!(!all.contains("more"));
+    assert_eq!(top.matches("… 1 more").count(), 4, "{top}");
+    assert_eq!(top.matches("… 2 more").count(), 1, "{top}");
+
+    // the most frequent row is kept, ties in the map's order
+    let onsets = top.split("\n\n").find(|s| s.starts_with("onsets")).unwrap();
+    assert!(onsets.contains(" k "), "{onsets}");
+    assert!(!onsets.contains(" p "), "{onsets}");
+}
+
+#[test]
+fn json_is_labelled() {
+    let json = serde_json::to_value(stats()).unwrap();
+
+    assert_eq!(
+        json.as_object().unwrap().keys().collect::<Vec<_>>(),
+        [
+            "bigrams",
+            "codas",
+            "coverage",
+            "onsets",
+            "syllable_count",
+            "syllables",
+            "tones",
+            "vowels",
+            "word_count",
+            "word_lengths",
+        ]
+    );
+    assert_eq!(json["word_count"], 3);
+    assert_eq!(json["onsets"]["k"], 3);
+    assert_eq!(json["tones"]["low"], 2);
+    assert_eq!(json["codas"]["none"], 4);
+    assert_eq!(json["word_lengths"]["2"], 2);
+    assert_eq!(
+        json["bigrams"][format!("{}-{}", wa::syllable("ka,"), wa::syllable("pa`"))],
+        2
+    );
+}
@@ -0,0 +1,105 @@
+use wa::{
+    romanization::{self, Romanization, Romanize},
+    syllabify, Expected, ParseMode, Syllable, Word, C, T, V,
+};
+
+#[test]
+fn letters_are_split_into_syllables() {
+    assert_eq!(syllabify("xu,yze~"), Ok(vec![wa::word("xu,y-ze~")]));
+    assert_eq!(syllabify("xúyzẽ"), Ok(vec![wa::word("xu,y-ze~")]));
+    assert_eq!(syllabify("ka,ya,"), Ok(vec![wa::word("ka,-ya,")]));
+    assert_eq!(syllabify("ka,yya,"), Ok(vec![wa::word("ka,y-ya,")]));
+    assert_eq!(syllabify("ka,"), Ok(vec![wa::word("ka,")]));
+}
+
+// a coda can't be followed by a vowel and a tone mark ends the vowel, so whatever follows a
+// syllable's coda starts the next one
+#[test]
+fn every_pair_of_syllables_splits_one_way() {
+    let schemes: [&dyn Romanization; 3] = [
+        &romanization::Unicode,
+        &romanization::Ascii,
+        &romanization::Numbered,
+    ];
+
+    for scheme in schemes {
+        for first in Syllable::all() {
+            for onset in C::all() {
+                let second = Syllable {
+                    onset,
+                    vowel: V::A,
+                    tone: T::High,
+                    coda: None,
+                };
+                let letters = format!("{}{}", first.romanize(scheme), second.romanize(scheme));
+
+                assert_eq!(
+                    syllabify(&letters),
+                    Ok(vec![Word(vec![first, second])]),
+                    "{letters}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn errors_are_reported_from_the_furthest_syllable() {
+    // xu,y splits off, then q is no onset
+    let error = syllabify("xu,yqze~").unwrap_err();
+    assert_eq!(error.span, 4..5);
+    assert_eq!(error.expected, Expected::Onset);
+    assert_eq!(error.found, Some('q'));
+
+    // ze has no tone mark
+    let error = syllabify("xu,yze").unwrap_err();
+    assert_eq!(error.span, 6..6);
+    assert_eq!(error.expected, Expected::Tone);
+    assert_eq!(error.found, None);
+
+    // a mark left over after a syllable is stray, not the start of the next one
+    let error = syllabify("ka,\u{308}").unwrap_err();
+    assert_eq!(error.span, 3..5);
+    assert_eq!(error.expected, Expected::End);
+    assert_eq!(
+        error.message(),
+        "stray combining mark '\u{25CC}\u{308}' (U+0308)"
+    );
+
+    let error = syllabify("ka,\u{301}").unwrap_err();
+    assert_eq!(
+        error.message(),
+        "doubled tone mark '\u{25CC}\u{301}' (U+0301)"
+    );
+
+    let error = syllabify("xu,yka,`ze~").unwrap_err();
+    assert_eq!(error.span, 7..8);
+    assert_eq!(error.message(), "doubled tone mark '`'");
+
+    // offsets are in bytes
+    let error = syllabify("xúyzẽq").unwrap_err();
+    assert_eq!(error.span, 8..9);
+    assert_eq!(error.found, Some('q'));
+}
+
+#[test]
+fn a_lenient_parse_splits_words() {
+    assert_eq!(
+        wa::parse_with::<Word>("xúyzẽ", ParseMode::Lenient),
+        Ok(wa::word("xu,y-ze~"))
+    );
+    assert_eq!(
+        wa::parse_with::<Word>("ka,-xu,yze~", ParseMode::Lenient),
+        Ok(wa::word("ka,-xu,y-ze~"))
+    );
+}
+
+#[test]
+fn a_strict_parse_asks_for_hyphens() {
+    let error = wa::parse_with::<Word>("xúyzẽ", ParseMode::Strict).unwrap_err();
+
+    assert_eq!(
+        error.note.as_deref(),
+        Some("syllables are separated by hyphens, as in xúy-zẽ")
+    );
+}
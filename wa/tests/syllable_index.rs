@@ -0,0 +1,57 @@
+use wa::{CStem, PureC, Syllable, C, H, T, V};
+
+#[test]
+fn every_syllable_round_trips_through_its_index() {
+    for syllable in Syllable::all() {
+        assert_eq!(Syllable::from_index(syllable.index()), Some(syllable));
+    }
+}
+
+#[test]
+fn all_syllables_are_listed_once_in_order() {
+    let all: Vec<Syllable> = Syllable::all().collect();
+
+    assert_eq!(all.len(), Syllable::COUNT);
+    assert_eq!(all.len(), 1900);
+
+    // strictly increasing, so sorted and without repeats
+    assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+
+    for (i, syllable) in all.iter().enumerate() {
+        assert_eq!(syllable.index() as usize, i);
+    }
+}
+
+#[test]
+fn indices_past_the_end_are_none() {
+    assert_eq!(Syllable::from_index(Syllable::COUNT as u16), None);
+    assert_eq!(Syllable::from_index(u16::MAX), None);
+}
+
+#[test]
+fn segments_round_trip_through_their_indices() {
+    fn check<X: Copy + PartialEq + std::fmt::Debug>(
+        all: impl Iterator<Item = X>,
+        count: usize,
+        index: impl Fn(&X) -> usize,
+        from_index: impl Fn(usize) -> Option<X>,
+    ) {
+        let all: Vec<X> = all.collect();
+
+        assert_eq!(all.len(), count);
+
+        for (i, x) in all.iter().enumerate() {
+            assert_eq!(index(x), i);
+            assert_eq!(from_index(i), Some(*x));
+        }
+
+        assert_eq!(from_index(count), None);
+    }
+
+    check(CStem::all(), CStem::COUNT, CStem::index, CStem::from_index);
+    check(PureC::all(), PureC::COUNT, PureC::index, PureC::from_index);
+    check(H::all(), H::COUNT, H::index, H::from_index);
+    check(C::all(), C::COUNT, C::index, C::from_index);
+    check(V::all(), V::COUNT, V::index, V::from_index);
+    check(T::all(), T::COUNT, T::index, T::from_index);
+}
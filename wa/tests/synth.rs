@@ -0,0 +1,29 @@
+#![cfg(feature = "synth")]
+
+use wa::synth::{Speak, Synthesizer, Voice};
+
+#[test]
+fn default_voice_is_valid() {
+    assert_eq!(Voice::default().validate(), Ok(()));
+    assert!(!wa::word("ka^-xu,y")
+        .speak(&Voice::default())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn voices_that_would_never_finish_are_rejected() {
+    let voices = [
+        r#"{"rate": 0}"#,
+        r#"{"rate": -1}"#,
+        r#"{"sample_rate": 0}"#,
+        r#"{"word_gap": -0.5}"#,
+    ];
+
+    for json in voices {
+        let voice: Voice = serde_json::from_str(json).unwrap();
+
+        assert!(Synthesizer::new(voice.clone()).is_err(), "{json}");
+        assert!(wa::syllable("ka^").speak(&voice).is_err(), "{json}");
+    }
+}